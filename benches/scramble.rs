@@ -0,0 +1,110 @@
+//! Time taken to parse, apply and draw scrambles of every event.
+//!
+//! Run natively with `cargo bench`, or in WebAssembly with
+//! `cargo bench --target wasm32-wasip1`, which runs under wasmtime as set up in
+//! `.cargo/config.toml`. Benchmarks can be filtered by name: `cargo bench -- 777`.
+//!
+//! Scrambles are the ones the tests of each puzzle check, from `src/puzzles/fixtures.rs`,
+//! repeated up to every length measured.
+
+use scr_to_svg::get_scramble_svg;
+use scr_to_svg::puzzles::{self, cube, megaminx};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[path = "../src/puzzles/fixtures.rs"]
+mod fixtures;
+
+/// Every event with the scrambles it is measured with.
+const EVENTS: [(&str, &[&str]); 10] = [
+    ("222", fixtures::TWO_BY_TWO),
+    ("333", fixtures::THREE_BY_THREE),
+    ("444", fixtures::FOUR_BY_FOUR),
+    ("555", fixtures::FIVE_BY_FIVE),
+    ("666", fixtures::SIX_BY_SIX),
+    ("777", fixtures::SEVEN_BY_SEVEN),
+    ("MEGA", fixtures::MEGAMINX),
+    ("223", fixtures::TWO_BY_TWO_BY_THREE),
+    ("332", fixtures::THREE_BY_THREE_BY_TWO),
+    ("334", fixtures::THREE_BY_THREE_BY_FOUR),
+];
+
+/// Number of moves in the scrambles measured.
+const LENGTHS: [usize; 3] = [20, 60, 120];
+
+/// How long each benchmark runs for at least.
+const TARGET: Duration = Duration::from_millis(200);
+
+/// Runs `f` until it has taken at least [`TARGET`], and prints the average time of a run.
+fn bench<T>(name: &str, filter: Option<&str>, mut f: impl FnMut() -> T) {
+    if filter.is_some_and(|filter| !name.contains(filter)) {
+        return;
+    }
+
+    let mut runs = 1_u32;
+    loop {
+        let start = Instant::now();
+        for _ in 0..runs {
+            black_box(f());
+        }
+        let elapsed = start.elapsed();
+
+        if elapsed >= TARGET {
+            println!(
+                "{name:<24} {:>12.1} µs/run",
+                elapsed.as_secs_f64() * 1e6 / runs as f64
+            );
+            return;
+        }
+        runs *= 2;
+    }
+}
+
+fn main() {
+    // Cargo passes `--bench`, anything else is a filter on the names.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let filter = args
+        .iter()
+        .find(|a| !a.starts_with("--"))
+        .map(String::as_str);
+
+    for (event, scrambles) in EVENTS {
+        let moves: Vec<&str> = scrambles
+            .iter()
+            .flat_map(|scramble| scramble.split_ascii_whitespace())
+            .collect();
+
+        for length in LENGTHS {
+            let scramble: Vec<&str> = moves.iter().copied().cycle().take(length).collect();
+            let scramble = scramble.join(" ");
+
+            if event == "MEGA" {
+                bench(&format!("parse {event} {length}"), filter, || {
+                    megaminx::parse_scramble(black_box(&scramble))
+                });
+            } else {
+                bench(&format!("parse {event} {length}"), filter, || {
+                    cube::parse_scramble(black_box(&scramble))
+                });
+            }
+
+            bench(&format!("apply {event} {length}"), filter, || {
+                let mut puzzle = puzzles::new(event).unwrap();
+                puzzle.apply_scramble(black_box(&scramble));
+                puzzle
+            });
+
+            let mut puzzle = puzzles::new(event).unwrap();
+            puzzle
+                .try_apply_scramble(&scramble)
+                .unwrap_or_else(|e| panic!("{event} scramble cannot be made: {e}"));
+            bench(&format!("draw {event} {length}"), filter, || {
+                puzzle.draw().to_string()
+            });
+
+            bench(&format!("svg {event} {length}"), filter, || {
+                get_scramble_svg(event, black_box(&scramble), None)
+            });
+        }
+    }
+}
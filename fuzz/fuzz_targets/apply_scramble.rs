@@ -0,0 +1,39 @@
+//! Applies any string as a scramble of every event, as is and read as an algorithm, which
+//! should never panic.
+//!
+//! Run with `cargo fuzz run apply_scramble` from the root of the repository. The corpus starts
+//! from the scrambles of the tests.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scr_to_svg::puzzles;
+use scr_to_svg::puzzles::alg::Alg;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(scramble) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let alg = Alg::parse(scramble).ok().map(|alg| alg.to_string());
+
+    for event in puzzles::events() {
+        let mut puzzle = puzzles::new(&event).unwrap();
+        let solved = puzzle.to_facelets();
+
+        match puzzle.try_apply_scramble(scramble) {
+            Ok(()) => {
+                puzzle.draw();
+            }
+            Err(e) => {
+                assert_eq!(puzzle.to_facelets(), solved, "{e}");
+                assert!(scramble.split_ascii_whitespace().nth(e.index()).is_some());
+            }
+        }
+
+        puzzles::new(&event).unwrap().apply_scramble(scramble);
+        if let Some(alg) = &alg {
+            puzzles::new(&event).unwrap().apply_scramble(alg);
+        }
+    }
+});
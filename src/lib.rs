@@ -3,6 +3,13 @@ extern crate core;
 pub mod puzzles;
 mod utils;
 
+pub use utils::color::Color;
+
+use puzzles::alg::Alg;
+use puzzles::bld::{Buffers, LetteringScheme, Memo};
+use puzzles::cube::{Cube, OrientationMarks, ThreeByThree};
+use puzzles::sheet::{draw_sheets, Paper, Round};
+use puzzles::{Puzzle, RenderOptions};
 use wasm_bindgen::prelude::*;
 
 #[cfg(feature = "wee_alloc")]
@@ -26,18 +33,269 @@ pub fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+/// The puzzle after the scramble, drawn with the named theme if one is given. Themes can be
+/// `light`, `dark`, `high-contrast`, `colorblind` or any registered with
+/// [`puzzles::register_theme`].
+#[wasm_bindgen]
+pub fn get_scramble_svg(event: &str, scramble: &str, theme: Option<String>) -> String {
+    set_panic_hook();
+
+    draw_scramble(event, scramble, theme_options(theme.as_deref()))
+}
+
+/// Like [`get_scramble_svg`], with the stickers of each face drawn as one path per color, a
+/// subpath per sticker or, for themes drawing no outlines, per rectangle of touching stickers, and
+/// the coordinates rounded to `precision` decimal places, for smaller documents.
+#[wasm_bindgen]
+pub fn get_compact_scramble_svg(
+    event: &str,
+    scramble: &str,
+    theme: Option<String>,
+    precision: Option<u8>,
+) -> String {
+    set_panic_hook();
+
+    let options = RenderOptions {
+        compact: true,
+        precision,
+        ..theme_options(theme.as_deref())
+    };
+    draw_scramble(event, scramble, options)
+}
+
+/// A case of an algorithm set, drawn with its recommended mask and the named theme if one is
+/// given. Cases are named like `PLL Ua` or `OLL 27`, and F2L, COLL, ZBLL, CLL and EG cases by
+/// their setup, such as `F2L R U' R'` or `COLL Sune R U2 R' U' R U' R'`.
+#[wasm_bindgen]
+pub fn get_case_svg(name: &str, theme: Option<String>) -> String {
+    set_panic_hook();
+
+    match puzzles::cases::case(name) {
+        Some(case) => case.draw_with(&theme_options(theme.as_deref())).to_string(),
+        None => {
+            log("Case not recognised.");
+            "".to_string()
+        }
+    }
+}
+
+/// The 3x3 after the scramble and after every step of the solution, `columns` to a row (4 if
+/// not given), each with the label of its step and its move count underneath. Steps are the
+/// lines of the solution, labelled by their comments such as `R U R' // pair 1`.
 #[wasm_bindgen]
-pub fn get_scramble_svg(event: &str, scramble: &str) -> String {
+pub fn get_reconstruction_svg(
+    scramble: &str,
+    solution: &str,
+    columns: Option<u32>,
+    theme: Option<String>,
+) -> String {
     set_panic_hook();
 
+    let solution = match Alg::parse(solution) {
+        Ok(solution) => solution,
+        Err(e) => {
+            log(&format!("Algorithm not recognised: {e}."));
+            return "".to_string();
+        }
+    };
+    let mut cube = Cube::<ThreeByThree>::new();
+    if let Err(e) = cube.try_apply_scramble(scramble) {
+        log(&format!("Scramble not recognised: {e}."));
+        return "".to_string();
+    }
+
+    let columns = columns.unwrap_or(4) as usize;
+    match cube.draw_solution(&solution, columns, &theme_options(theme.as_deref())) {
+        Ok(svg) => svg.to_string(),
+        Err(e) => {
+            log(&format!("Solution not recognised: {e}."));
+            "".to_string()
+        }
+    }
+}
+
+/// Scramble sheets of a round on `A4` or `Letter` paper, one SVG per page, with the scrambles
+/// and the extra scrambles drawn in a table under the competition and round names.
+#[wasm_bindgen]
+pub fn get_scramble_sheets(
+    event: &str,
+    competition: &str,
+    round: &str,
+    set: &str,
+    scrambles: Vec<String>,
+    extras: Vec<String>,
+    paper: &str,
+) -> Vec<String> {
+    set_panic_hook();
+
+    let paper = match paper {
+        "" | "A4" => Paper::A4,
+        "Letter" => Paper::Letter,
+        _ => {
+            log("Paper not recognised.");
+            return vec![];
+        }
+    };
+    let round = Round {
+        competition: competition.to_string(),
+        name: round.to_string(),
+        set: set.to_string(),
+    };
+    let scrambles: Vec<&str> = scrambles.iter().map(String::as_str).collect();
+    let extras: Vec<&str> = extras.iter().map(String::as_str).collect();
+
+    match draw_sheets(
+        event,
+        &round,
+        &scrambles,
+        &extras,
+        paper,
+        &RenderOptions::default(),
+    ) {
+        Ok(sheets) => sheets.iter().map(|s| s.to_string()).collect(),
+        Err(e) => {
+            log(&format!("Sheet not made: {e}."));
+            vec![]
+        }
+    }
+}
+
+fn theme_options(theme: Option<&str>) -> RenderOptions {
+    match theme {
+        None | Some("") => RenderOptions::default(),
+        Some(name) => puzzles::theme(name).unwrap_or_else(|| {
+            log("Theme not recognised.");
+            RenderOptions::default()
+        }),
+    }
+}
+
+fn draw_scramble(event: &str, scramble: &str, options: RenderOptions) -> String {
     let puzzle = puzzles::new(event);
 
     if let Some(mut p) = puzzle {
-        p.apply_scramble(scramble);
+        if let Err(e) = p.try_apply_scramble(scramble) {
+            log(&format!("Scramble not recognised: {e}."));
+            return "".to_string();
+        }
 
-        p.draw().to_string()
+        p.draw_with(&options).to_string()
     } else {
         log("Event not recognised.");
         "".to_string()
     }
 }
+
+/// State of the puzzle after the scramble, as a JSON document.
+#[wasm_bindgen]
+pub fn get_scramble_state(event: &str, scramble: &str) -> String {
+    set_panic_hook();
+
+    let puzzle = puzzles::new(event);
+
+    if let Some(mut p) = puzzle {
+        if let Err(e) = p.try_apply_scramble(scramble) {
+            log(&format!("Scramble not recognised: {e}."));
+            return "".to_string();
+        }
+
+        p.to_json()
+    } else {
+        log("Event not recognised.");
+        "".to_string()
+    }
+}
+
+/// Length of a scramble or solution for the event's puzzle in every metric, as a JSON document
+/// such as `{"htm":7,"qtm":8,"stm":7,"etm":7,"sqtm":8}`.
+#[wasm_bindgen]
+pub fn get_move_count(event: &str, moves: &str) -> String {
+    set_panic_hook();
+
+    let Some(puzzle) = puzzles::new(event) else {
+        log("Event not recognised.");
+        return "".to_string();
+    };
+    match puzzle.move_count(moves) {
+        Ok(count) => count.to_json(),
+        Err(e) => {
+            log(&format!("Scramble not recognised: {e}."));
+            "".to_string()
+        }
+    }
+}
+
+/// The algorithm with its comments left out and its groups, commutators and conjugates
+/// expanded, as moves separated by spaces.
+#[wasm_bindgen]
+pub fn get_expanded_alg(alg: &str) -> String {
+    set_panic_hook();
+
+    match Alg::parse(alg) {
+        Ok(alg) => alg.to_string(),
+        Err(e) => {
+            log(&format!("Algorithm not recognised: {e}."));
+            "".to_string()
+        }
+    }
+}
+
+/// Blindfolded memo for a 3x3 scramble, from the given corner and edge buffers such as `UFR`
+/// and `UF`. Empty buffers use the defaults.
+#[wasm_bindgen]
+pub fn get_scramble_memo(scramble: &str, corner_buffer: &str, edge_buffer: &str) -> String {
+    set_panic_hook();
+
+    match bld_cube(scramble, corner_buffer, edge_buffer) {
+        Some((_, memo)) => memo.to_string(),
+        None => "".to_string(),
+    }
+}
+
+/// The 3x3 scramble with its blindfolded memo written under the net.
+#[wasm_bindgen]
+pub fn get_scramble_svg_with_memo(
+    scramble: &str,
+    corner_buffer: &str,
+    edge_buffer: &str,
+) -> String {
+    set_panic_hook();
+
+    match bld_cube(scramble, corner_buffer, edge_buffer) {
+        Some((cube, memo)) => cube
+            .draw_with_caption(&memo.to_string(), &RenderOptions::default())
+            .to_string(),
+        None => "".to_string(),
+    }
+}
+
+fn bld_cube(
+    scramble: &str,
+    corner_buffer: &str,
+    edge_buffer: &str,
+) -> Option<(Cube<ThreeByThree>, Memo)> {
+    let corner_buffer = if corner_buffer.is_empty() {
+        "UFR"
+    } else {
+        corner_buffer
+    };
+    let edge_buffer = if edge_buffer.is_empty() {
+        "UF"
+    } else {
+        edge_buffer
+    };
+    let Some(buffers) = Buffers::new(corner_buffer, edge_buffer) else {
+        log("Buffer not recognised.");
+        return None;
+    };
+
+    let mut cube = Cube::<ThreeByThree>::new();
+    cube.track_orientation(OrientationMarks::Hidden);
+    if let Err(e) = cube.try_apply_scramble(scramble) {
+        log(&format!("Scramble not recognised: {e}."));
+        return None;
+    }
+    let memo = cube.memo(&LetteringScheme::default(), &buffers)?;
+
+    Some((cube, memo))
+}
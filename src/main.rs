@@ -1,7 +1,7 @@
 use scr_to_svg::get_scramble_svg;
 
 fn main() {
-    let svg = get_scramble_svg("333", "");
+    let svg = get_scramble_svg("333", "", None);
 
     println!("{svg}");
 }
@@ -0,0 +1,414 @@
+use crate::puzzles::AlgError;
+use std::fmt;
+
+/// Most moves the groups of an algorithm can expand to together.
+const MAX_MOVES: usize = 10_000;
+/// Most brackets a bracket can be inside of.
+const MAX_DEPTH: usize = 100;
+
+/// An algorithm or reconstruction, with its grouping and commutators expanded to flat moves.
+///
+/// Moves are read as they are written between spaces, brackets and comments, so any notation
+/// can be used: the puzzle checks them when they are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alg {
+    pub steps: Vec<Step>,
+}
+
+/// The moves of one line of an algorithm, labelled by the comment that ends it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    pub label: Option<String>,
+    pub moves: Vec<String>,
+}
+
+impl Alg {
+    /// Reads an algorithm written with:
+    /// - comments from `//` to the end of the line, which label the moves of their line, such
+    ///   as `R U R' // pair 1`;
+    /// - groups `(R U R' U')` and `[R U]`, repeated a number of times with `(R U)3` and
+    ///   inverted with `(R U)'`;
+    /// - commutators `[A, B]`, for `A B A' B'`, and conjugates `[A: B]`, for `A B A'`.
+    ///
+    /// Lines break steps outside of brackets only, lines without moves are left out.
+    pub fn parse(text: &str) -> Result<Alg, AlgError> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            depth: 0,
+            label: None,
+            total: 0,
+        };
+
+        let mut steps = vec![];
+        loop {
+            let (moves, end) = parser.sequence(&['\n'])?;
+            let label = parser.label.take();
+            if !moves.is_empty() {
+                steps.push(Step { label, moves });
+            }
+            if end.is_none() {
+                return Ok(Alg { steps });
+            }
+        }
+    }
+
+    /// Every move of every step.
+    pub fn moves(&self) -> impl Iterator<Item = &str> {
+        self.steps
+            .iter()
+            .flat_map(|s| s.moves.iter().map(String::as_str))
+    }
+}
+
+/// The moves separated by spaces, as read by [`crate::puzzles::Puzzle::apply_scramble`].
+impl fmt::Display for Alg {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.moves().collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// The moves separated by spaces.
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.moves.join(" "))
+    }
+}
+
+/// The move turning the other way: `'` is added or removed, `++` and `--` swapped, and half
+/// turns are left as they are.
+pub(crate) fn inverse(mv: &str) -> String {
+    if let Some(mv) = mv.strip_suffix("++") {
+        format!("{mv}--")
+    } else if let Some(mv) = mv.strip_suffix("--") {
+        format!("{mv}++")
+    } else if let Some(mv) = mv.strip_suffix('\'') {
+        mv.to_string()
+    } else if mv.ends_with('2') {
+        mv.to_string()
+    } else {
+        format!("{mv}'")
+    }
+}
+
+fn invert(moves: &[String]) -> Vec<String> {
+    moves.iter().rev().map(|mv| inverse(mv)).collect()
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Number of brackets the text being read is inside of.
+    depth: usize,
+    /// The last comment read, labelling the step being read.
+    label: Option<String>,
+    /// Number of moves the groups read so far outside of brackets expand to.
+    total: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Reads moves up to one of the `ends`, which is skipped and given back, or up to the end
+    /// of the text.
+    fn sequence(&mut self, ends: &[char]) -> Result<(Vec<String>, Option<char>), AlgError> {
+        let mut moves = vec![];
+
+        while let Some(c) = self.peek() {
+            if ends.contains(&c) {
+                self.pos += 1;
+                return Ok((moves, Some(c)));
+            }
+
+            match c {
+                '/' if self.chars.get(self.pos + 1) == Some(&'/') => self.comment(),
+                '(' => {
+                    let open = self.pos;
+                    self.pos += 1;
+                    let (group, _) = self.enclosed(open, &[')'])?;
+                    self.repeat(open, group, &mut moves)?;
+                }
+                '[' => {
+                    let open = self.pos;
+                    self.pos += 1;
+                    let (a, end) = self.enclosed(open, &[',', ':', ']'])?;
+                    let group = if end == ']' {
+                        a
+                    } else {
+                        let (b, _) = self.enclosed(open, &[']'])?;
+                        let mut group = [a.clone(), b.clone(), invert(&a)].concat();
+                        if end == ',' {
+                            group.extend(invert(&b));
+                        }
+                        group
+                    };
+                    self.repeat(open, group, &mut moves)?;
+                }
+                ')' | ']' | ',' | ':' | '/' => {
+                    return Err(AlgError::Unexpected {
+                        index: self.pos,
+                        found: c,
+                    })
+                }
+                c if c.is_whitespace() => self.pos += 1,
+                _ => moves.push(self.token()),
+            }
+        }
+
+        Ok((moves, None))
+    }
+
+    /// Reads moves up to one of the `ends` of the bracket opened at `open`.
+    fn enclosed(&mut self, open: usize, ends: &[char]) -> Result<(Vec<String>, char), AlgError> {
+        if self.depth == MAX_DEPTH {
+            return Err(AlgError::Nesting { index: open });
+        }
+
+        self.depth += 1;
+        let sequence = self.sequence(ends);
+        self.depth -= 1;
+
+        match sequence? {
+            (moves, Some(end)) => Ok((moves, end)),
+            (_, None) => Err(AlgError::Unclosed {
+                index: open,
+                found: self.chars[open],
+            }),
+        }
+    }
+
+    /// Repeats and inverts the group opened at `open` as its closing bracket is followed, and
+    /// adds it to the moves read before it.
+    ///
+    /// The groups outside of brackets are counted together, and the groups in a bracket
+    /// together with them, so that the whole algorithm stays within [`MAX_MOVES`].
+    fn repeat(
+        &mut self,
+        open: usize,
+        moves: Vec<String>,
+        read: &mut Vec<String>,
+    ) -> Result<(), AlgError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        let times = if self.pos == start {
+            Some(1)
+        } else {
+            let times: String = self.chars[start..self.pos].iter().collect();
+            times.parse::<usize>().ok()
+        };
+        let held = if self.depth == 0 {
+            self.total
+        } else {
+            self.total + read.len()
+        };
+        let times = times
+            .filter(|times| moves.len().saturating_mul(*times) <= MAX_MOVES.saturating_sub(held))
+            .ok_or(AlgError::Length { index: open })?;
+
+        let moves = if self.peek() == Some('\'') {
+            self.pos += 1;
+            invert(&moves)
+        } else {
+            moves
+        };
+        let length = moves.len() * times;
+        if self.depth == 0 {
+            self.total += length;
+        }
+        read.extend(moves.into_iter().cycle().take(length));
+        Ok(())
+    }
+
+    fn comment(&mut self) {
+        let start = self.pos + 2;
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.pos += 1;
+        }
+
+        let comment: String = self.chars[start..self.pos].iter().collect();
+        let comment = comment.trim();
+        if !comment.is_empty() {
+            self.label = Some(comment.to_string());
+        }
+    }
+
+    fn token(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| !c.is_whitespace() && !"()[],:/".contains(c))
+        {
+            self.pos += 1;
+        }
+
+        self.chars[start..self.pos].iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::{Cube, ThreeByThree};
+    use crate::puzzles::megaminx::Megaminx;
+    use crate::puzzles::Puzzle;
+
+    fn expand(text: &str) -> String {
+        Alg::parse(text).unwrap().to_string()
+    }
+
+    #[test]
+    fn notation() {
+        assert_eq!(expand("R U R' U'"), "R U R' U'");
+        assert_eq!(expand("(U2) R' F R F'"), "U2 R' F R F'");
+        assert_eq!(expand("(R U)3"), "R U R U R U");
+        assert_eq!(expand("(R U2 Rw')'"), "Rw U2 R'");
+        assert_eq!(expand("(R U)2' F"), "U' R' U' R' F");
+        assert_eq!(expand("[R, U]"), "R U R' U'");
+        assert_eq!(expand("[R U R': D]"), "R U R' D R U' R'");
+        assert_eq!(expand("[F: [R, U]]"), "F R U R' U' F'");
+        assert_eq!(
+            expand("[R' D' R, U2]2"),
+            "R' D' R U2 R' D R U2 ".repeat(2).trim()
+        );
+        assert_eq!(expand("[x:(R U)0]y"), "x x' y");
+        assert_eq!(expand("R ()99999999999 U"), "R U");
+        assert_eq!(expand("R++ [D++, U]"), "R++ D++ U D-- U'");
+        assert_eq!(expand(""), "");
+    }
+
+    #[test]
+    fn steps() {
+        let alg = Alg::parse(
+            "// solve\n\
+             y' R' F R // cross\n\
+             \n\
+             U R U' R'\n\
+             (R U\n  R' U') // pair 1\n\
+             [F: [R, U]] //   OLL   \n",
+        )
+        .unwrap();
+
+        let steps: Vec<_> = alg
+            .steps
+            .iter()
+            .map(|s| (s.label.as_deref(), s.to_string()))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (Some("cross"), "y' R' F R".to_string()),
+                (None, "U R U' R'".to_string()),
+                (Some("pair 1"), "R U R' U'".to_string()),
+                (Some("OLL"), "F R U R' U' F'".to_string()),
+            ]
+        );
+        assert_eq!(alg.moves().count(), 18);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| Alg::parse(text).unwrap_err();
+
+        assert_eq!(
+            error("R (U R'"),
+            AlgError::Unclosed {
+                index: 2,
+                found: '('
+            }
+        );
+        assert_eq!(
+            error("[R, [U: F]"),
+            AlgError::Unclosed {
+                index: 0,
+                found: '['
+            }
+        );
+        assert_eq!(
+            error("R U)"),
+            AlgError::Unexpected {
+                index: 3,
+                found: ')'
+            }
+        );
+        assert_eq!(
+            error("[R, U, F]"),
+            AlgError::Unexpected {
+                index: 5,
+                found: ','
+            }
+        );
+        assert_eq!(
+            error("(R, U)"),
+            AlgError::Unexpected {
+                index: 2,
+                found: ','
+            }
+        );
+        assert_eq!(
+            error("R / U"),
+            AlgError::Unexpected {
+                index: 2,
+                found: '/'
+            }
+        );
+        assert_eq!(error("(R U)5001"), AlgError::Length { index: 0 });
+        assert_eq!(
+            expand(&format!("{}R{}", "(".repeat(100), ")".repeat(100))),
+            "R"
+        );
+        assert_eq!(
+            error(&format!("{}R{}", "[".repeat(101), "]".repeat(101))),
+            AlgError::Nesting { index: 100 }
+        );
+        assert_eq!(
+            error("R [(R U)99999999999999999999999, U]").to_string(),
+            "group at index 3 expands to too many moves"
+        );
+        assert_eq!(
+            error("[[[[(R U R' U')50, U]10, R], U]10, D]"),
+            AlgError::Length { index: 1 }
+        );
+        assert_eq!(
+            error(&"(R U R' U')2500 ".repeat(2000)),
+            AlgError::Length { index: 16 }
+        );
+        assert_eq!(
+            error(&format!("({})0", "(R U)2500 ".repeat(3))),
+            AlgError::Length { index: 21 }
+        );
+        assert_eq!(
+            expand(&format!("R {}", "(R U)2500 ".repeat(2))).len(),
+            "R ".len() + "R U ".len() * 5000 - 1
+        );
+    }
+
+    #[test]
+    fn applied() {
+        let solved = Cube::<ThreeByThree>::new().state;
+        for text in ["[R, U]6", "(R U R' U')6", "[R: U]4", "(U2)2'"] {
+            let mut cube = Cube::<ThreeByThree>::new();
+            cube.try_apply_scramble(&expand(text)).unwrap();
+            assert_eq!(cube.state, solved, "{text}");
+        }
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.try_apply_scramble(&expand("[R U2: D' (R U)2']"))
+            .unwrap();
+        assert_ne!(cube.state, solved);
+        cube.try_apply_scramble(&expand("[R U2: D' (R U)2']'"))
+            .unwrap();
+        assert_eq!(cube.state, solved);
+
+        let mut minx = Megaminx::new();
+        minx.try_apply_scramble(&expand("[R++ D--, U] ([R++ D--, U])'"))
+            .unwrap();
+        assert_eq!(minx.to_facelets(), Megaminx::new().to_facelets());
+    }
+}
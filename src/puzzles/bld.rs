@@ -0,0 +1,740 @@
+use crate::puzzles::cube::{
+    permutation_parity, scale, Cube, Face, Position, Sticker, ThreeByThree, CORNERS, EDGES,
+};
+use crate::puzzles::Puzzle;
+use ndarray::Array3;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Faces in the order letters are given to them.
+const LETTER_ORDER: [Face; 6] = [Face::U, Face::L, Face::F, Face::R, Face::B, Face::D];
+
+/// Memo letters for every corner and edge sticker.
+///
+/// Letters go face by face in U L F R B D order, and clockwise on each face from the top left
+/// corner or the top edge. Wings use the edge letters, on the sticker of the wing that comes
+/// first going clockwise around its face.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LetteringScheme {
+    corners: [char; 24],
+    edges: [char; 24],
+}
+
+impl Default for LetteringScheme {
+    fn default() -> Self {
+        LetteringScheme::speffz()
+    }
+}
+
+impl LetteringScheme {
+    /// Letters A to X on both corners and edges.
+    pub fn speffz() -> Self {
+        let letters = core::array::from_fn(|i| (b'A' + i as u8) as char);
+        LetteringScheme {
+            corners: letters,
+            edges: letters,
+        }
+    }
+
+    /// A scheme from 24 corner and 24 edge letters, returns `None` if either has the wrong
+    /// length.
+    pub fn new(corners: &str, edges: &str) -> Option<Self> {
+        let letters =
+            |s: &str| -> Option<[char; 24]> { s.chars().collect::<Vec<_>>().try_into().ok() };
+        Some(LetteringScheme {
+            corners: letters(corners)?,
+            edges: letters(edges)?,
+        })
+    }
+
+    /// The letter of the corner sticker at `(face, row, col)` on a cube of the given size.
+    pub fn corner(&self, position: Position, size: usize) -> Option<char> {
+        letter_index(position, size, 0).map(|i| self.corners[i])
+    }
+
+    /// The letter of the edge sticker at `(face, row, col)`, either a middle edge or the
+    /// lettered sticker of a wing.
+    pub fn edge(&self, position: Position, size: usize) -> Option<char> {
+        (1..size)
+            .take_while(|depth| 2 * depth < size)
+            .find_map(|depth| letter_index(position, size, depth))
+            .map(|i| self.edges[i])
+    }
+
+    /// The letter of a center sticker, by its place among the four stickers of its face
+    /// a quarter turn apart: corner letters on the diagonals of the face and edge letters
+    /// elsewhere. Middle centers have no letter.
+    pub fn center(&self, (face, row, col): Position, size: usize) -> Option<char> {
+        let last = size - 1;
+        if row == 0 || col == 0 || row == last || col == last {
+            return None;
+        }
+
+        let rotations = [
+            (row, col),
+            (col, last - row),
+            (last - row, last - col),
+            (last - col, row),
+        ];
+        let turns = rotations.iter().position(|(r, c)| r <= c && r + c < last)?;
+        let face = LETTER_ORDER.iter().position(|f| *f == face)?;
+
+        let letters = if row == col || row + col == last {
+            &self.corners
+        } else {
+            &self.edges
+        };
+        Some(letters[face * 4 + (4 - turns) % 4])
+    }
+}
+
+/// Index of a sticker among the 24 lettered stickers `depth` away from the corners, clockwise
+/// from the top left of each face: 0 for corners, `size / 2` for middle edges.
+fn letter_index((face, row, col): Position, size: usize, depth: usize) -> Option<usize> {
+    let face = LETTER_ORDER.iter().position(|f| *f == face)?;
+    let slot = slots(size, depth)
+        .iter()
+        .position(|slot| *slot == (row, col))?;
+    Some(face * 4 + slot)
+}
+
+fn slots(size: usize, depth: usize) -> [(usize, usize); 4] {
+    let last = size - 1;
+    [
+        (0, depth),
+        (depth, last),
+        (last, last - depth),
+        (last - depth, 0),
+    ]
+}
+
+/// Where the pieces of a cube went, as found by tracking sticker identities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermutationReport {
+    pub corners: OrbitReport,
+    /// Middle edges, on odd cubes from the 3x3.
+    pub edges: Option<OrbitReport>,
+    /// Wing orbits, from the one closest to the corners inwards.
+    pub wings: Vec<OrbitReport>,
+}
+
+/// Pieces out of place in one orbit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrbitReport {
+    pub cycles: Vec<Cycle>,
+    /// Pieces in their place but twisted or flipped, with the letter of the position and by
+    /// how many stickers the piece is turned clockwise.
+    pub twisted: Vec<(char, u8)>,
+    /// Whether the pieces are an odd permutation.
+    pub parity: bool,
+}
+
+/// Pieces going round each other, lettered by the sticker each one goes to, starting from
+/// the first sticker of the first position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cycle {
+    pub letters: Vec<char>,
+    /// By how many stickers the piece coming back to the first position is turned.
+    pub twist: u8,
+}
+
+/// Pieces the memo starts from, and the ones the parity algorithm swaps them with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Buffers {
+    corner: usize,
+    edge: usize,
+    parity_corner: usize,
+    parity_edge: usize,
+}
+
+impl Default for Buffers {
+    /// UFR and UF, with UBR and UR for parity.
+    fn default() -> Self {
+        Buffers::new("UFR", "UF").unwrap()
+    }
+}
+
+impl Buffers {
+    /// Buffers from piece names such as `UFR` or `DF`, with faces in any order. Parity swaps
+    /// them with UBR and UR, or with UFR and UF when those are the buffers.
+    pub fn new(corner: &str, edge: &str) -> Option<Self> {
+        let corner = piece_index(&CORNERS.map(|(name, _)| name), corner)?;
+        let edge = piece_index(&EDGES.map(|(name, _)| name), edge)?;
+        let other = |buffer, default, fallback| if buffer == default { fallback } else { default };
+
+        Some(Buffers {
+            corner,
+            edge,
+            parity_corner: other(corner, 3, 0),
+            parity_edge: other(edge, 0, 1),
+        })
+    }
+
+    /// Sets the pieces the parity algorithm swaps with the buffers.
+    pub fn with_parity(self, corner: &str, edge: &str) -> Option<Self> {
+        let parity_corner = piece_index(&CORNERS.map(|(name, _)| name), corner)?;
+        let parity_edge = piece_index(&EDGES.map(|(name, _)| name), edge)?;
+        if parity_corner == self.corner || parity_edge == self.edge {
+            return None;
+        }
+
+        Some(Buffers {
+            parity_corner,
+            parity_edge,
+            ..self
+        })
+    }
+}
+
+fn piece_index(names: &[&str], name: &str) -> Option<usize> {
+    let sorted = |s: &str| {
+        let mut chars: Vec<char> = s.chars().map(|c| c.to_ascii_uppercase()).collect();
+        chars.sort_unstable();
+        chars
+    };
+    names.iter().position(|n| sorted(n) == sorted(name))
+}
+
+/// Blindfolded memo for a 3x3, solving edges then corners from the buffers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Memo {
+    pub edges: OrbitMemo,
+    pub corners: OrbitMemo,
+    /// With parity, both orbits are memoed as if the buffers had swapped places with the
+    /// parity pieces, to be fixed by a parity algorithm such as the Jb perm at the end.
+    pub parity: bool,
+}
+
+/// Targets for one kind of piece.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrbitMemo {
+    pub targets: Vec<char>,
+    /// Indices of the targets that start a new cycle.
+    pub breaks: Vec<usize>,
+    /// Pieces solved apart from their orientation, lettered by the sticker where their U or
+    /// D sticker is, or their F or B sticker for middle layer edges.
+    pub twisted: Vec<char>,
+}
+
+impl OrbitMemo {
+    /// Targets grouped in letter pairs, the last one on its own with an odd number of targets.
+    pub fn pairs(&self) -> Vec<String> {
+        self.targets
+            .chunks(2)
+            .map(|pair| pair.iter().collect())
+            .collect()
+    }
+}
+
+impl fmt::Display for Memo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Edges:")?;
+        for pair in self.edges.pairs() {
+            write!(f, " {pair}")?;
+        }
+        if !self.edges.twisted.is_empty() {
+            write!(f, "\nFlips: {}", String::from_iter(&self.edges.twisted))?;
+        }
+        write!(f, "\nCorners:")?;
+        for pair in self.corners.pairs() {
+            write!(f, " {pair}")?;
+        }
+        if !self.corners.twisted.is_empty() {
+            write!(f, "\nTwists: {}", String::from_iter(&self.corners.twisted))?;
+        }
+        if self.parity {
+            write!(f, "\nParity")?;
+        }
+        Ok(())
+    }
+}
+
+/// One orbit of pieces, each given by its stickers in clockwise order.
+pub(crate) struct Orbit {
+    pieces: Vec<Vec<Position>>,
+    /// For every position, the piece on it and the index of its sticker on the first sticker
+    /// of the position.
+    state: Vec<(usize, usize)>,
+}
+
+impl Orbit {
+    fn new(pieces: Vec<Vec<Position>>, identity: &Array3<Sticker>) -> Self {
+        let index: HashMap<Position, (usize, usize)> = pieces
+            .iter()
+            .enumerate()
+            .flat_map(|(i, piece)| piece.iter().enumerate().map(move |(k, p)| (*p, (i, k))))
+            .collect();
+
+        let state = pieces
+            .iter()
+            .map(|piece| {
+                let (face, row, col) = piece[0];
+                let sticker = identity[[face as usize, row, col]];
+                index[&(sticker.face, sticker.row, sticker.col)]
+            })
+            .collect();
+
+        Orbit { pieces, state }
+    }
+
+    fn corners(identity: &Array3<Sticker>) -> Self {
+        let size = identity.dim().1;
+        let pieces = CORNERS
+            .iter()
+            .map(|(_, corner)| corner.iter().map(|p| scale(*p, size)).collect())
+            .collect();
+        Orbit::new(pieces, identity)
+    }
+
+    fn edges(identity: &Array3<Sticker>) -> Self {
+        let size = identity.dim().1;
+        let pieces = EDGES
+            .iter()
+            .map(|(_, edge)| edge.iter().map(|p| scale(*p, size)).collect())
+            .collect();
+        Orbit::new(pieces, identity)
+    }
+
+    fn wings(identity: &Array3<Sticker>, depth: usize) -> Self {
+        let size = identity.dim().1;
+        let pieces = LETTER_ORDER
+            .iter()
+            .flat_map(|face| {
+                slots(size, depth)
+                    .into_iter()
+                    .map(move |(row, col)| vec![(*face, row, col)])
+            })
+            .collect();
+        Orbit::new(pieces, identity)
+    }
+
+    /// The sticker that goes to `(position, sticker)`'s place, as a position and sticker of
+    /// the piece it belongs to.
+    fn target(&self, position: usize, sticker: usize) -> (usize, usize) {
+        let (piece, k) = self.state[position];
+        let len = self.pieces[piece].len();
+        (piece, (k + sticker) % len)
+    }
+
+    fn len(&self) -> usize {
+        self.pieces.len()
+    }
+
+    fn sticker(&self, position: usize, sticker: usize) -> Position {
+        self.pieces[position][sticker]
+    }
+
+    fn report(&self, letter: impl Fn(Position) -> Option<char>) -> OrbitReport {
+        let letter = |(i, k): (usize, usize)| letter(self.sticker(i, k)).unwrap_or('?');
+        let mut visited = vec![false; self.len()];
+        let mut cycles = vec![];
+        let mut twisted = vec![];
+
+        for start in 0..self.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+
+            let (piece, k) = self.state[start];
+            if piece == start {
+                if k != 0 {
+                    twisted.push((letter((start, 0)), k as u8));
+                }
+                continue;
+            }
+
+            let mut letters = vec![letter((start, 0))];
+            let mut current = self.target(start, 0);
+            while current.0 != start {
+                visited[current.0] = true;
+                letters.push(letter(current));
+                current = self.target(current.0, current.1);
+            }
+            cycles.push(Cycle {
+                letters,
+                twist: current.1 as u8,
+            });
+        }
+
+        OrbitReport {
+            cycles,
+            twisted,
+            parity: self.parity(),
+        }
+    }
+
+    fn parity(&self) -> bool {
+        let permutation: Vec<usize> = self.state.iter().map(|(piece, _)| *piece).collect();
+        permutation_parity(&permutation)
+    }
+    /// Shoots to every unsolved piece from `buffer`, breaking into a new cycle whenever the
+    /// buffer piece comes home early. With `swap`, the buffer piece and that piece are
+    /// considered to belong at each other's positions.
+    fn memo(
+        &self,
+        buffer: usize,
+        swap: Option<usize>,
+        letter: impl Fn(Position) -> Option<char>,
+    ) -> OrbitMemo {
+        let letter = |(i, k): (usize, usize)| letter(self.sticker(i, k)).unwrap_or('?');
+        let mut state = self.state.clone();
+        if let Some(other) = swap {
+            for (piece, _) in state.iter_mut() {
+                if *piece == buffer {
+                    *piece = other;
+                } else if *piece == other {
+                    *piece = buffer;
+                }
+            }
+        }
+
+        let mut targets = vec![];
+        let mut breaks = vec![];
+        loop {
+            let (piece, k) = state[buffer];
+            let (position, s) = if piece != buffer {
+                (piece, k)
+            } else {
+                match (0..self.len()).find(|&i| i != buffer && state[i].0 != i) {
+                    Some(i) => {
+                        breaks.push(targets.len());
+                        (i, 0)
+                    }
+                    None => break,
+                }
+            };
+            targets.push(letter((position, s)));
+
+            // The buffer sticker goes to the target sticker, which takes the piece there back
+            // to the buffer.
+            let n = self.pieces[position].len();
+            let (other, m) = state[position];
+            state[position] = (piece, (k + n - s) % n);
+            state[buffer] = (other, (m + s) % n);
+        }
+
+        let twisted = (0..self.len())
+            .filter(|&i| i != buffer && state[i].0 == i && state[i].1 != 0)
+            .map(|i| {
+                let n = self.pieces[i].len();
+                letter((i, (n - state[i].1) % n))
+            })
+            .collect();
+
+        OrbitMemo {
+            targets,
+            breaks,
+            twisted,
+        }
+    }
+}
+
+impl<T> Cube<T>
+where
+    usize: From<T>,
+    T: Default,
+{
+    /// The cube rotated so that its centers are where they started, since pieces are solved
+    /// relative to them. Even cubes have no fixed centers and are left as they are.
+    fn centered(&self) -> Cube<T> {
+        let size = self.state.dim().1;
+        if size % 2 == 0 {
+            return self.clone();
+        }
+
+        let c = size / 2;
+        for x in ["", "x", "x2", "x'", "z", "z'"] {
+            for y in ["", "y", "y2", "y'"] {
+                let mut cube = self.clone();
+                cube.apply_scramble(&format!("{x} {y}"));
+                if (0..6).all(|f| cube.state[[f, c, c]] == f) {
+                    return cube;
+                }
+            }
+        }
+        unreachable!("some rotation brings the centers of a cube home")
+    }
+
+    /// Cycles, twisted pieces and parity of every corner, edge and wing orbit, lettered with
+    /// the given scheme relative to the centers. Returns `None` unless sticker identities are
+    /// tracked, see [`Cube::track_orientation`].
+    pub fn permutation_report(&self, scheme: &LetteringScheme) -> Option<PermutationReport> {
+        let cube = self.centered();
+        let identity = cube.orientation()?;
+        let size = identity.dim().1;
+
+        let corners = Orbit::corners(identity).report(|p| scheme.corner(p, size));
+        let edges = (size % 2 == 1 && size > 1)
+            .then(|| Orbit::edges(identity).report(|p| scheme.edge(p, size)));
+        let wings = (1..size)
+            .take_while(|depth| 2 * depth < size - 1)
+            .map(|depth| Orbit::wings(identity, depth).report(|p| scheme.edge(p, size)))
+            .collect();
+
+        Some(PermutationReport {
+            corners,
+            edges,
+            wings,
+        })
+    }
+}
+
+impl Cube<ThreeByThree> {
+    /// Edge and corner memo from the given buffers, lettered with the given scheme relative to
+    /// the centers. Returns `None` unless sticker identities are tracked, see
+    /// [`Cube::track_orientation`].
+    pub fn memo(&self, scheme: &LetteringScheme, buffers: &Buffers) -> Option<Memo> {
+        let cube = self.centered();
+        let identity = cube.orientation()?;
+        let corners = Orbit::corners(identity);
+        let edges = Orbit::edges(identity);
+
+        let parity = corners.parity();
+        let swap = |piece| parity.then_some(piece);
+
+        Some(Memo {
+            edges: edges.memo(buffers.edge, swap(buffers.parity_edge), |p| {
+                scheme.edge(p, 3)
+            }),
+            corners: corners.memo(buffers.corner, swap(buffers.parity_corner), |p| {
+                scheme.corner(p, 3)
+            }),
+            parity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::{FourByFour, OrientationMarks, ThreeByThree, TwoByTwo};
+    use crate::puzzles::{Puzzle, RenderOptions};
+
+    fn scrambled<T: Default>(scramble: &str) -> PermutationReport
+    where
+        usize: From<T>,
+        Cube<T>: Puzzle,
+    {
+        let mut cube = Cube::<T>::new();
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.apply_scramble(scramble);
+        cube.permutation_report(&LetteringScheme::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn solved() {
+        let cube = Cube::<ThreeByThree>::new();
+        assert_eq!(cube.permutation_report(&LetteringScheme::speffz()), None);
+
+        let report = scrambled::<ThreeByThree>("");
+        assert!(report.corners.cycles.is_empty());
+        assert!(!report.corners.parity);
+        assert_eq!(report.edges.unwrap().cycles, vec![]);
+        assert!(report.wings.is_empty());
+    }
+
+    #[test]
+    fn t_perm() {
+        let report = scrambled::<ThreeByThree>("R U R' U' R' F R2 U' R' U' R U R' F'");
+
+        assert_eq!(
+            report.corners.cycles,
+            vec![Cycle {
+                letters: vec!['C', 'B'],
+                twist: 0
+            }]
+        );
+        assert!(report.corners.parity);
+
+        let edges = report.edges.unwrap();
+        assert_eq!(
+            edges.cycles,
+            vec![Cycle {
+                letters: vec!['B', 'D'],
+                twist: 0
+            }]
+        );
+        assert!(edges.twisted.is_empty());
+        assert!(edges.parity);
+    }
+
+    #[test]
+    fn twists_and_flips() {
+        // Superflip, then URF and UBR twisted in opposite directions.
+        let report = scrambled::<ThreeByThree>(
+            "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 \
+             R' D R D' R' D R U R' D' R D R' D' R U'",
+        );
+
+        let edges = report.edges.unwrap();
+        assert!(edges.cycles.is_empty());
+        assert_eq!(edges.twisted.len(), 12);
+        assert!(edges.twisted.iter().all(|(_, flip)| *flip == 1));
+
+        assert_eq!(report.corners.twisted, vec![('C', 2), ('B', 1)]);
+    }
+
+    #[test]
+    fn lettering_scheme() {
+        let scheme = LetteringScheme::speffz();
+        assert_eq!(scheme.corner((Face::U, 0, 0), 3), Some('A'));
+        assert_eq!(scheme.corner((Face::D, 2, 0), 3), Some('X'));
+        assert_eq!(scheme.corner((Face::F, 1, 0), 3), None);
+        assert_eq!(scheme.edge((Face::R, 1, 2), 3), Some('N'));
+        assert_eq!(scheme.edge((Face::L, 0, 1), 4), Some('E'));
+        assert_eq!(scheme.edge((Face::L, 0, 2), 4), None);
+
+        assert!(LetteringScheme::new("abc", "def").is_none());
+        let custom =
+            LetteringScheme::new("ABCDEFGHIJKLMNOPQRSTUVWX", "abcdefghijklmnopqrstuvwx").unwrap();
+        assert_eq!(custom.edge((Face::U, 1, 2), 3), Some('b'));
+    }
+
+    #[test]
+    fn big_cubes() {
+        let report = scrambled::<TwoByTwo>("R U R' U'");
+        assert_eq!(report.edges, None);
+        assert!(report.wings.is_empty());
+        assert_eq!(report.corners.cycles.len(), 2);
+
+        let report = scrambled::<FourByFour>("R U R' U' R' F R2 U' R' U' R U R' F'");
+        assert_eq!(report.edges, None);
+        assert_eq!(report.wings.len(), 1);
+        let wings = &report.wings[0];
+        assert_eq!(wings.cycles.len(), 2);
+        assert!(!wings.parity);
+
+        let report = scrambled::<FourByFour>("Rw R'");
+        assert_eq!(report.wings[0].cycles.len(), 1);
+        assert_eq!(report.wings[0].cycles[0].letters.len(), 4);
+        assert!(report.wings[0].parity);
+    }
+
+    fn memo(scramble: &str, buffers: &Buffers) -> Memo {
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.apply_scramble(scramble);
+        cube.memo(&LetteringScheme::default(), buffers).unwrap()
+    }
+
+    #[test]
+    fn cycle_breaks() {
+        let memo = memo("D2", &Buffers::default());
+
+        assert_eq!(memo.edges.targets, vec!['V', 'X', 'V', 'U', 'W', 'U']);
+        assert_eq!(memo.edges.breaks, vec![0, 3]);
+        assert_eq!(memo.corners.targets, vec!['V', 'X', 'V', 'U', 'W', 'U']);
+        assert!(!memo.parity);
+        assert_eq!(memo.to_string(), "Edges: VX VU WU\nCorners: VX VU WU");
+
+        assert_eq!(
+            Cube::<ThreeByThree>::new().memo(&LetteringScheme::default(), &Buffers::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn rotated() {
+        let scramble = "R U2 F' D L2 B R' U' F2 D2";
+        let memo = memo(&format!("{scramble} L"), &Buffers::default());
+        assert_eq!(
+            memo,
+            self::memo(&format!("{scramble} Rw"), &Buffers::default())
+        );
+        assert_eq!(
+            memo,
+            self::memo(&format!("{scramble} L x'"), &Buffers::default())
+        );
+        assert_eq!(
+            self::memo("x y2 R U R' U' y2 x'", &Buffers::default()),
+            self::memo("x y2 R U R' U'", &Buffers::default())
+        );
+
+        assert_eq!(
+            scrambled::<ThreeByThree>(&format!("{scramble} L")),
+            scrambled::<ThreeByThree>(&format!("{scramble} Rw z2 y"))
+        );
+    }
+
+    #[test]
+    fn parity() {
+        let memo = memo("R U R' U' R' F R2 U' R' U' R U R' F'", &Buffers::default());
+
+        assert!(memo.parity);
+        assert_eq!(memo.edges.targets, vec!['B', 'D']);
+        assert!(memo.corners.targets.is_empty());
+        assert_eq!(memo.to_string(), "Edges: BD\nCorners:\nParity");
+    }
+
+    #[test]
+    fn twists_and_flips_memo() {
+        let memo = memo(
+            "U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2 \
+             R' D R D' R' D R U R' D' R D R' D' R U'",
+            &Buffers::default(),
+        );
+
+        assert!(memo.edges.targets.is_empty());
+        assert_eq!(memo.edges.twisted.len(), 11);
+        assert!(memo.corners.targets.is_empty());
+        assert_eq!(memo.corners.twisted, vec!['N']);
+    }
+
+    #[test]
+    fn buffers() {
+        assert_eq!(Buffers::new("fur", "FU"), Some(Buffers::default()));
+        assert_eq!(Buffers::new("UF", "UF"), None);
+        assert_eq!(Buffers::default().with_parity("UFR", "UR"), None);
+
+        let buffers = Buffers::new("UBL", "DF").unwrap();
+        let memo = memo("D2", &buffers);
+        assert_eq!(memo.edges.targets, vec!['W', 'V', 'X', 'V']);
+        assert_eq!(memo.edges.breaks, vec![1]);
+        assert_eq!(memo.corners.breaks, vec![0, 3]);
+    }
+
+    #[test]
+    fn caption() {
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.apply_scramble("D2");
+        let memo = cube
+            .memo(&LetteringScheme::default(), &Buffers::default())
+            .unwrap();
+        let svg = cube
+            .draw_with_caption(&memo.to_string(), &RenderOptions::default())
+            .to_string();
+
+        assert!(svg.contains("viewBox=\"0 0 130 120\""));
+        assert!(svg.contains("\nEdges: VX VU WU\n"));
+        assert!(svg.contains("\nCorners: VX VU WU\n"));
+
+        let svg = cube
+            .draw_with_caption("R & U <x/>", &RenderOptions::default())
+            .to_string();
+        assert!(svg.contains("\nR &amp; U &lt;x/&gt;\n"));
+        assert!(!svg.contains("<x/>"));
+    }
+
+    #[test]
+    fn center_letters() {
+        let scheme = LetteringScheme::speffz();
+        assert_eq!(scheme.center((Face::U, 1, 1), 3), None);
+        assert_eq!(scheme.center((Face::U, 0, 1), 3), None);
+        assert_eq!(scheme.center((Face::U, 1, 1), 4), Some('A'));
+        assert_eq!(scheme.center((Face::U, 1, 2), 4), Some('B'));
+        assert_eq!(scheme.center((Face::F, 2, 1), 4), Some('L'));
+        assert_eq!(scheme.center((Face::R, 1, 2), 5), Some('M'));
+        assert_eq!(scheme.center((Face::R, 2, 3), 5), Some('N'));
+        assert_eq!(scheme.center((Face::D, 3, 1), 6), Some('X'));
+    }
+}
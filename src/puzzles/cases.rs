@@ -0,0 +1,1261 @@
+use crate::puzzles::cube::{Cube, Face, Mask, OrientationMarks, ThreeByThree, TwoByTwo};
+use crate::puzzles::{Puzzle, RenderOptions};
+use lazy_static::lazy_static;
+use ndarray::Array3;
+use std::fmt;
+use svg::node::element::SVG;
+
+/// Sets of algorithm cases, each with the cube it is on and the stickers that tell its cases
+/// apart.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Group {
+    F2l,
+    Oll,
+    Pll,
+    Coll,
+    Zbll,
+    /// 2x2 CLL.
+    Cll,
+    /// 2x2 EG-1, CLL with an adjacent swap in the D layer.
+    Eg1,
+    /// 2x2 EG-2, CLL with a diagonal swap in the D layer.
+    Eg2,
+}
+
+impl Group {
+    /// Number of layers of the cube the cases are on.
+    pub fn size(self) -> usize {
+        match self {
+            Group::Cll | Group::Eg1 | Group::Eg2 => 2,
+            _ => 3,
+        }
+    }
+
+    /// Stickers to show when drawing a case. The mask applies to where pieces start from, so
+    /// that the pieces it shows are drawn wherever the setup has taken them.
+    pub fn mask(self) -> Option<Mask> {
+        match self {
+            Group::F2l => Some(Mask::F2l),
+            Group::Oll => Some(Mask::Oll),
+            Group::Pll => Some(Mask::Pll),
+            Group::Coll => Some(Mask::Coll),
+            Group::Zbll => Some(Mask::Zbll),
+            Group::Cll => Some(Mask::Cll),
+            Group::Eg1 | Group::Eg2 => None,
+        }
+    }
+
+    /// Whether cases are drawn from above, as on last layer algorithm sheets.
+    fn last_layer(self) -> bool {
+        !matches!(self, Group::F2l | Group::Eg1 | Group::Eg2)
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Group::F2l => "F2L",
+            Group::Oll => "OLL",
+            Group::Pll => "PLL",
+            Group::Coll => "COLL",
+            Group::Zbll => "ZBLL",
+            Group::Cll => "CLL",
+            Group::Eg1 => "EG-1",
+            Group::Eg2 => "EG-2",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A named case of an algorithm set, as left by its setup on a solved cube.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// The group then the name of the case in it, such as `PLL Ua` or `OLL 27`. Groups without a
+    /// numbering everyone agrees on name their cases by the setup, after the shape of the U face
+    /// for last layer subsets, such as `F2L R U' R'` or `COLL Sune R U2 R' U' R U' R'`.
+    pub name: String,
+    pub group: Group,
+    pub setup: &'static str,
+}
+
+impl Case {
+    /// The recommended mask, see [`Group::mask`].
+    pub fn mask(&self) -> Option<Mask> {
+        self.group.mask()
+    }
+
+    pub fn draw(&self) -> SVG {
+        self.draw_with(&RenderOptions::default())
+    }
+
+    /// Draws the case with its mask: from above for last layer cases, with arrows for PLLs, or
+    /// else as a net.
+    pub fn draw_with(&self, options: &RenderOptions) -> SVG {
+        match self.group.size() {
+            2 => self.draw_on(Cube::<TwoByTwo>::new(), options),
+            _ => self.draw_on(Cube::<ThreeByThree>::new(), options),
+        }
+    }
+
+    fn draw_on<T>(&self, mut cube: Cube<T>, options: &RenderOptions) -> SVG
+    where
+        usize: From<T>,
+        T: Default,
+    {
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.try_apply_scramble(self.setup)
+            .expect("case setups are valid moves");
+
+        if let Some(mask) = self.mask() {
+            let visible = mask.visible(cube.state.dim().1);
+            let origins = cube.orientation().expect("orientation is tracked");
+            let mask = origins.map(|s| visible[[s.face as usize, s.row, s.col]]);
+            cube.set_mask(Some(Mask::Custom(mask)))
+                .expect("the mask is mapped from one of the cube's size");
+        }
+
+        if self.group.last_layer() {
+            cube.draw_last_layer(self.group == Group::Pll, options)
+        } else {
+            cube.draw_with(options)
+        }
+    }
+}
+
+lazy_static! {
+    static ref CASES: Vec<Case> = catalogue();
+}
+
+/// Every case of every group.
+pub fn cases() -> &'static [Case] {
+    &CASES
+}
+
+/// The case with the given name, whatever its letter case and spacing, such as `pll ua`.
+pub fn case(name: &str) -> Option<&'static Case> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    CASES.iter().find(|c| c.name.eq_ignore_ascii_case(&name))
+}
+
+fn catalogue() -> Vec<Case> {
+    let mut cases = vec![];
+    let mut add = |group, name: String, setup| {
+        cases.push(Case {
+            name: format!("{group} {name}"),
+            group,
+            setup,
+        })
+    };
+
+    for setup in F2L {
+        add(Group::F2l, setup.to_string(), setup);
+    }
+    for (i, setup) in OLL.into_iter().enumerate() {
+        add(Group::Oll, (i + 1).to_string(), setup);
+    }
+    for (name, setup) in PLL {
+        add(Group::Pll, name.to_string(), setup);
+    }
+
+    let subsets = [
+        (Group::Coll, COLL),
+        (Group::Zbll, ZBLL),
+        (Group::Cll, CLL),
+        (Group::Eg1, EG_1),
+        (Group::Eg2, EG_2),
+    ];
+    for (group, shapes) in subsets {
+        for (shape, setups) in shapes {
+            for setup in setups.iter() {
+                add(group, format!("{shape} {setup}"), setup);
+            }
+        }
+    }
+
+    cases
+}
+
+/// A turn of the U face adjusting the last layer before or after an algorithm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Auf {
+    None,
+    U,
+    U2,
+    UPrime,
+}
+
+impl Auf {
+    /// Every AUF, from the least turning.
+    const ALL: [Auf; 4] = [Auf::None, Auf::U, Auf::UPrime, Auf::U2];
+
+    fn inverse(self) -> Auf {
+        match self {
+            Auf::U => Auf::UPrime,
+            Auf::UPrime => Auf::U,
+            auf => auf,
+        }
+    }
+}
+
+/// The move, or nothing for [`Auf::None`].
+impl fmt::Display for Auf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mv = match self {
+            Auf::None => "",
+            Auf::U => "U",
+            Auf::U2 => "U2",
+            Auf::UPrime => "U'",
+        };
+        write!(f, "{mv}")
+    }
+}
+
+/// The last layer of a cube with its first two layers solved, as found by [`recognise`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LastLayer {
+    /// Solved once `auf` is made.
+    Solved { auf: Auf },
+    /// Not oriented, `pre_auf` turns it into the OLL case as set up in the library.
+    Oll { case: &'static Case, pre_auf: Auf },
+    /// Oriented, `pre_auf` turns it into the PLL case as set up in the library, and `post_auf`
+    /// solves it after the algorithm of the case.
+    Pll {
+        case: &'static Case,
+        pre_auf: Auf,
+        post_auf: Auf,
+    },
+}
+
+/// Finds the OLL case of the last layer, or its PLL case once it is oriented. The cube can be
+/// turned any way around U, its centers telling which side is in front. `None` if the first
+/// two layers are not solved, or the last layer is not one a cube can have.
+///
+/// Symmetric cases can be seen from more than one AUF, the least turning ones are given.
+pub fn recognise(cube: &Cube<ThreeByThree>) -> Option<LastLayer> {
+    let after = |state: &Array3<Face>, moves: &str| {
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.state = state.clone();
+        cube.apply_scramble(moves);
+        cube.state
+    };
+    let setup = |moves: &str| after(&Cube::<ThreeByThree>::new().state, moves);
+
+    let mut state = cube.state.clone();
+    for _ in 0..3 {
+        if state[[Face::F as usize, 1, 1]] != Face::F {
+            state = after(&state, "y");
+        }
+    }
+
+    let solved = setup("");
+    let kept = Mask::F2l.visible(3);
+    let first_two = state.iter().zip(&solved).zip(&kept);
+    if first_two
+        .filter(|(_, kept)| **kept)
+        .any(|((face, solved), _)| face != solved)
+    {
+        return None;
+    }
+
+    let cases_of = |group| cases().iter().filter(move |c| c.group == group);
+    let oriented = |state: &Array3<Face>| state.map(|f| *f == Face::U);
+
+    if oriented(&state) != oriented(&solved) {
+        let setups: Vec<_> = cases_of(Group::Oll)
+            .map(|c| (c, oriented(&setup(c.setup))))
+            .collect();
+
+        return Auf::ALL.into_iter().find_map(|pre_auf| {
+            let turned = oriented(&after(&state, &pre_auf.to_string()));
+            let (case, _) = setups.iter().find(|(_, s)| *s == turned)?;
+            Some(LastLayer::Oll { case, pre_auf })
+        });
+    }
+
+    if let Some(auf) = Auf::ALL
+        .into_iter()
+        .find(|auf| after(&state, &auf.to_string()) == solved)
+    {
+        return Some(LastLayer::Solved { auf });
+    }
+
+    // A PLL seen after `post_auf` is its setup made from the solved cube turned the other way.
+    let setups: Vec<_> = Auf::ALL
+        .into_iter()
+        .flat_map(|post_auf| {
+            cases_of(Group::Pll)
+                .map(move |c| (c, post_auf, format!("{} {}", post_auf.inverse(), c.setup)))
+        })
+        .map(|(case, post_auf, moves)| (case, post_auf, setup(&moves)))
+        .collect();
+
+    Auf::ALL.into_iter().find_map(|pre_auf| {
+        let turned = after(&state, &pre_auf.to_string());
+        let (case, post_auf, _) = setups.iter().find(|(_, _, s)| *s == turned)?;
+        Some(LastLayer::Pll {
+            case,
+            pre_auf,
+            post_auf: *post_auf,
+        })
+    })
+}
+
+/// Setups of cases by the shape of their U face, those of OLL 21 to 27: H, Pi, U, T, L,
+/// Antisune and Sune. Cases are named by their shape then their setup.
+type Shapes = &'static [(&'static str, &'static [&'static str])];
+
+/// Setups of the F2L cases for the front right slot: first with both pieces in the U layer, then
+/// with only the edge there, only the corner there, and both in the slot. Cases are named by their
+/// setup.
+const F2L: [&str; 41] = [
+    "R U' R'",
+    "F' U' F",
+    "R U R'",
+    "F' U F",
+    "R' F R F2 U2 F",
+    "R U' R' F' U F",
+    "F R' F' R2 U2 R'",
+    "R' F R F2 U F",
+    "F' U F U2 R U R'",
+    "R U' R' U R U R'",
+    "R U' R' U' R U R'",
+    "R U' R' U F' U2 F",
+    "F' U' F U2 F' U F",
+    "R2 U2 F R2 F' U2 R2",
+    "F' U F U F' U' F",
+    "R U R' U2 R U' R'",
+    "R U' R' U2 F' U' F",
+    "R U R' U2 R U2 R'",
+    "F' U F U F' U F",
+    "F U F2 U F2 U2 F'",
+    "R2 U R' U R U2 R2",
+    "R U' R' U R U2 R'",
+    "F2 U' F U' F' U2 F2",
+    "F' U F U' F' U2 F",
+    "R' F R F2 U' F",
+    "R U' R' F' U' F",
+    "F' U F R U R'",
+    "R U2 R' F' U2 F",
+    "R' F R F' R U R'",
+    "R U R' U' F' U' F",
+    "R U' R' F' U2 F",
+    "R U R' U' F' U F",
+    "R U' R' U' R U2 R'",
+    "R' F R F' R U' R'",
+    "R U R' U2 R U R'",
+    "R2 U R2 U R2 U2 R2",
+    "R F U R U' R' F' U' R'",
+    "R U2 R U2 F R F' U2 R2",
+    "R U F R U R' U' F' R'",
+    "R U2 R U R' U R U2 R2",
+    "R U' R U2 F R2 F' U2 R2",
+];
+
+/// Setups of OLL 1 to 57, the inverses of their usual algorithms.
+const OLL: [&str; 57] = [
+    "F R' F' R U2 F R' F' R2 U2 R'",
+    "Fw U R U' R' Fw' F U R U' R' F'",
+    "F U R U' R' F' U Fw U R U' R' Fw'",
+    "F U R U' R' F' U' Fw U R U' R' Fw'",
+    "Rw' U' R U' R' U2 Rw",
+    "Rw U R' U R U2 Rw'",
+    "Rw U2 R' U' R U' Rw'",
+    "Lw' U2 L U L' U Lw",
+    "F U R U' R2 F' R U R U' R'",
+    "R U2 R' F R' F' R U' R U' R'",
+    "Rw U2 R' F R' F' R U' R U' Rw'",
+    "Rw R' U R' U2 R U R' U R M",
+    "R U R' U' R' F R2 U R' U' F'",
+    "F U F' R' F R U' R' F' R",
+    "Lw' U' Lw U' L' U L Lw' U Lw",
+    "Rw U Rw' U R U' R' Rw U' Rw'",
+    "F R' F' R U2 F R' F' R U' R U' R'",
+    "Rw' U2 R U R' U Rw2 U2 R' U' R U' Rw'",
+    "F R' F' R M U R U' R' U' R' Rw",
+    "M U R U R' U' M2 U R U' Rw'",
+    "R U R' U R U' R' U R U2 R'",
+    "R' U2 R2 U R2 U R2 U2 R'",
+    "R' U2 R' D' R U2 R' D R2",
+    "F R' F' Rw U R U' Rw'",
+    "R' F' Rw U R U' Rw' F",
+    "R U R' U R U2 R'",
+    "R U2 R' U' R U' R'",
+    "R U R' U' M' U R U' Rw'",
+    "R U' R' F' U F R U R' U R U' R'",
+    "F2 R U' R' U R U R2 F' R F'",
+    "R' F R U R' U' F' U R",
+    "L F' L' U' L U F U' L'",
+    "F R' F' R U R U' R'",
+    "F U R' U' R' F' R U R2 U' R'",
+    "R U2 R' F R' F' R2 U2 R'",
+    "F' L F L' U' L' U' L U L' U L",
+    "R U R' U' R' F R F'",
+    "F R' F' R U R U R' U' R U' R'",
+    "L U F' U' L' U L F L'",
+    "R' U' F U R U' R' F' R",
+    "F U R U' R' F' R U2 R' U' R U' R'",
+    "F U R U' R' F' R' U2 R U R' U R",
+    "F' L' U' L U F",
+    "F R U R' U' F'",
+    "F U R U' R' F'",
+    "R' U' F R' F' R U R",
+    "R' U' F R' F' R F R' F' R U R",
+    "F U R U' R' U R U' R' F'",
+    "Rw' U Rw2 U' Rw2 U' Rw2 U Rw'",
+    "Rw U' Rw2 U Rw2 U Rw2 U' Rw",
+    "F R U R' U' R U R' U' F'",
+    "R B U B' U R' U' R U' R'",
+    "Lw' U' L U' L' U L U' L' U2 Lw",
+    "Rw U R' U R U' R' U R U2 Rw'",
+    "R U' R' U' R U R2 F R2 U R' U' R' F' R",
+    "Rw' U' Rw R' U' R U R' U' R U Rw' U Rw",
+    "Rw U R' U' M U R U' R'",
+];
+
+/// Setups of the PLL cases, the inverses of their usual algorithms.
+const PLL: [(&str, &str); 21] = [
+    ("Aa", "x R2 D2 R U R' D2 R U' R x'"),
+    ("Ab", "x R' U R' D2 R U' R' D2 R2 x'"),
+    ("E", "x' D R U R' D' R U' R' D R U' R' D' R U R' x"),
+    ("F", "R' U' R U' R' U R U R2 F' R U R U' R' F U R"),
+    ("Ga", "D R' U' R D' U R2 U R' U R U' R U' R2"),
+    ("Gb", "D' R2 U R' U R' U' R U' R2 D U' R' U R"),
+    ("Gc", "D' R U R' D U' R2 U' R U' R' U R' U R2"),
+    ("Gd", "D R2 U' R U' R U R' U R2 D' U R U' R'"),
+    ("H", "M2 U' M2 U2 M2 U' M2"),
+    ("Ja", "x U2 Rw' U' Rw U2 R' F R' F' R2 x'"),
+    ("Jb", "R U R2 F' R U R U' R' F R U' R'"),
+    ("Na", "R U R' U2 R U R2 F' R U R U' R' F R U' R' U' R U' R'"),
+    ("Nb", "R' U R' F R F' R U' R' F' U F R U R' U' R"),
+    ("Ra", "R U2 R D R' U R D' R' U' R' U R U R'"),
+    ("Rb", "R' U2 R U2 R' F R U R' U' R' F' R2"),
+    ("T", "F R U' R' U R U R2 F' R U R U' R'"),
+    ("Ua", "M2 U' M U2 M' U' M2"),
+    ("Ub", "M2 U M U2 M' U M2"),
+    ("V", "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R'"),
+    ("Y", "F R' F' R U R U' R' F R U' R' U R U R' F'"),
+    ("Z", "M2 U2 M U' M2 U' M2 U' M"),
+];
+
+/// Setups of the COLL cases by the shape of the U face, `O` having every corner oriented.
+const COLL: Shapes = &[
+    (
+        "H",
+        &[
+            "R U R' U R U' R' U R U2 R'",
+            "R U R2 F' R U R U' R' F U R U' R' U R U2 R'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U2 R' U R U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U' R' U R U2 R'",
+        ],
+    ),
+    (
+        "Pi",
+        &[
+            "R' U2 R2 U R2 U R2 U2 R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R2 U R2 U R2 U2 R'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R2 U R2 U R2 U2 R'",
+        ],
+    ),
+    (
+        "U",
+        &[
+            "R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R' D' R U2 R' D R2",
+            "R' U R' F R F' R U' R' F' U F R U R' U R' D' R U2 R' D R2",
+        ],
+    ),
+    (
+        "T",
+        &[
+            "F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' F R' F' Rw U R U' Rw'",
+            "x R' U R' D2 R U' R' D2 R2 x' F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' U' F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' U2 F R' F' Rw U R U' Rw'",
+            "F R' F' R U R U' R' F R U' R' U R U R2 F' Rw U R U' Rw'",
+        ],
+    ),
+    (
+        "L",
+        &[
+            "R' F' Rw U R U' Rw' F",
+            "x R' U R' D2 R U' R' D2 R2 x' R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' U' R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' F' Rw U R U' Rw' F",
+            "R' U R' F R F' R U' R' F' U F R U R' U' F' Rw U R U' Rw' F",
+        ],
+    ),
+    (
+        "Antisune",
+        &[
+            "R U R' U R U2 R'",
+            "R U R2 F' R U R U' R' F U R U2 R'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U R'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U2 R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U R' U R U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U R' U R U2 R'",
+        ],
+    ),
+    (
+        "Sune",
+        &[
+            "R U2 R' U' R U' R'",
+            "R U R2 F' R U R U' R' F R U R' U' R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' R U2 R' U' R U' R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U2 R' U' R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U2 R' U' R U' R'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U' R' U' R U' R'",
+        ],
+    ),
+    (
+        "O",
+        &[
+            "x R2 D2 R U R' D2 R U' R x'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R'",
+        ],
+    ),
+];
+
+/// Setups of the T, U and L subsets of ZBLL.
+const ZBLL: Shapes = &[
+    (
+        "U",
+        &[
+            "R' U2 R' D' R U2 R' D R2",
+            "M2 U' M U2 M' U' M2 R' U2 R' D' R U2 R' D R2",
+            "M2 U' M2 U2 M2 U' M2 R' U2 R' D' R U2 R' D R2",
+            "M2 U M U2 M' U M2 R' U2 R' D' R U2 R' D R2",
+            "M2 U M U2 M' U M2 U R' U2 R' D' R U2 R' D R2",
+            "M2 U' M U2 M' U' M2 U R' U2 R' D' R U2 R' D R2",
+            "M2 U M U2 M' U M2 U2 R' U2 R' D' R U2 R' D R2",
+            "M2 U' M U2 M' U' M2 U' R' U2 R' D' R U2 R' D R2",
+            "M2 U' M U2 M' U' M2 U2 R' U2 R' D' R U2 R' D R2",
+            "M2 U M U2 M' U M2 U' R' U2 R' D' R U2 R' D R2",
+            "M2 U2 M U' M2 U' M2 U' M R' U2 R' D' R U2 R' D R2",
+            "M2 U2 M U' M2 U' M2 U' M U R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' U R' U2 R' D' R U2 R' D R2",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R' D' R U2 R' D R2",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U' R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' U2 R' U2 R' D' R U2 R' D R2",
+            "R U R2 F' R U R U' R' F R U' R2 U2 R' D' R U2 R' D R2",
+            "R' U2 R U2 R' F R U R' U' R' F' R U2 R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' U' R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U R' U2 R' D' R U2 R' D R2",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U' R' U2 R' D' R U2 R' D R2",
+            "F R U' R' U R U R2 F' R U R U' R2 U2 R' D' R U2 R' D R2",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U R' U2 R' D' R U2 R' D R2",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U2 R' U2 R' D' R U2 R' D R2",
+            "R U R2 F' R U R U' R' F R U' R' U' R' U2 R' D' R U2 R' D R2",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U' R' U2 R' D' R U2 R' D R2",
+            "D R2 U' R U' R U R' U R2 D' U R U' R2 U2 R' D' R U2 R' D R2",
+            "D' R U R' D U' R2 U' R U' R' U R' U R U2 R' D' R U2 R' D R2",
+            "D R' U' R D' U R2 U R' U R U' R U' R U2 R' D' R U2 R' D R2",
+            "R U2 R D R' U R D' R' U' R' U R U R2 U2 R' D' R U2 R' D R2",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U R' U2 R' D' R U2 R' D R2",
+            "R U R2 F' R U R U' R' F R U' R' U R' U2 R' D' R U2 R' D R2",
+            "R U R2 F' R U R U' R' F R U' R' U2 R' U2 R' D' R U2 R' D R2",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U2 R' U2 R' D' R U2 R' D R2",
+            "R' U R' F R F' R U' R' F' U F R U R' U R' D' R U2 R' D R2",
+            "F R U' R' U R U R2 F' R U R U' R' U' R' U2 R' D' R U2 R' D R2",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U' R' D' R U2 R' D R2",
+            "F R U' R' U R U R2 F' R U R U' R' U R' U2 R' D' R U2 R' D R2",
+            "F R U' R' U R U R2 F' R U R U' R' U2 R' U2 R' D' R U2 R' D R2",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R2 U2 R' D' R U2 R' D R2",
+            "R U2 R D R' U R D' R' U' R' U R U R' U' R' U2 R' D' R U2 R' D R2",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U' R' U2 R' D' R U2 R' D R2",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U' R' U2 R' D' R U2 R' D R2",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U' R' U2 R' D' R U2 R' D R2",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U' R' U2 R' D' R U2 R' D R2",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U R' U2 R' D' R U2 R' D R2",
+            "R U2 R D R' U R D' R' U' R' U R U R' U R' U2 R' D' R U2 R' D R2",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U R' U2 R' D' R U2 R' D R2",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U R' U2 R' D' R U2 R' D R2",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U R' U2 R' D' R U2 R' D R2",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U2 R' U2 R' D' R U2 R' D R2",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U2 R' U2 R' D' R U2 R' D R2",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U2 R' U2 R' D' R U2 R' D R2",
+            "R U2 R D R' U R D' R' U' R' U R U R' U2 R' U2 R' D' R U2 R' D R2",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U2 R' U2 R' D' R U2 R' D R2",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U2 R' U2 R' D' R U2 R' D R2",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U R' U2 R' D' R U2 R' D R2",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' R' U2 R' D' R U2 R' D R2",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U' R' U2 R' D' R U2 R' D R2",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U' R' U2 R' D' R U2 R' D R2",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U2 R' U2 R' D' R U2 R' D R2",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U R' U2 R' D' R U2 R' D R2",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x R' U2 R' D' R U2 R' D R2",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U' R' U2 R' D' R U2 R' D R2",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U R' U2 R' D' R U2 R' D R2",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U2 R' U2 R' D' R U2 R' D R2",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x U R' U2 R' D' R U2 R' D R2",
+            "R U R' U2 R U R2 F' R U R U' R' F R U' R' U' R U' R2 U2 R' D' R U2 R' D R2",
+        ],
+    ),
+    (
+        "T",
+        &[
+            "F R' F' Rw U R U' Rw'",
+            "M2 U' M U2 M' U' M2 F R' F' Rw U R U' Rw'",
+            "M2 U M U2 M' U M2 F R' F' Rw U R U' Rw'",
+            "M2 U' M2 U2 M2 U' M2 F R' F' Rw U R U' Rw'",
+            "M2 U M U2 M' U M2 U2 F R' F' Rw U R U' Rw'",
+            "M2 U M U2 M' U M2 U' F R' F' Rw U R U' Rw'",
+            "M2 U' M U2 M' U' M2 U F R' F' Rw U R U' Rw'",
+            "M2 U' M U2 M' U' M2 U2 F R' F' Rw U R U' Rw'",
+            "M2 U M U2 M' U M2 U F R' F' Rw U R U' Rw'",
+            "M2 U' M U2 M' U' M2 U' F R' F' Rw U R U' Rw'",
+            "M2 U2 M U' M2 U' M2 U' M F R' F' Rw U R U' Rw'",
+            "M2 U2 M U' M2 U' M2 U' M U F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' F R' F' Rw U R U' Rw'",
+            "x R' U R' D2 R U' R' D2 R2 x' F R' F' Rw U R U' Rw'",
+            "x R' U R' D2 R U' R' D2 R2 x' U' F R' F' Rw U R U' Rw'",
+            "x R' U R' D2 R U' R' D2 R2 x' U2 F R' F' Rw U R U' Rw'",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' U' F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' U2 F R' F' Rw U R U' Rw'",
+            "x R' U R' D2 R U' R' D2 R2 x' U F R' F' Rw U R U' Rw'",
+            "x R2 D2 R U R' D2 R U' R x' U F R' F' Rw U R U' Rw'",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U F R' F' Rw U R U' Rw'",
+            "R U R2 F' R U R U' R' F R U' R' F R' F' Rw U R U' Rw'",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 F R' F' Rw U R U' Rw'",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U' F R' F' Rw U R U' Rw'",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U2 F R' F' Rw U R U' Rw'",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U F R' F' Rw U R U' Rw'",
+            "R U R2 F' R U R U' R' F R U' R' U F R' F' Rw U R U' Rw'",
+            "F R U' R' U R U R2 F' R U R U' R' F R' F' Rw U R U' Rw'",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U' F R' F' Rw U R U' Rw'",
+            "R U R2 F' R U R U' R' F R U' R' U' F R' F' Rw U R U' Rw'",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U2 F R' F' Rw U R U' Rw'",
+            "R U R2 F' R U R U' R' F R U' R' U2 F R' F' Rw U R U' Rw'",
+            "F R' F' R U R U' R' F R U' R' U R U R2 F' Rw U R U' Rw'",
+            "F R U' R' U R U R2 F' R U R U' R' U F R' F' Rw U R U' Rw'",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' F R' F' Rw U R U' Rw'",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 F R' F' Rw U R U' Rw'",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 F R' F' Rw U R U' Rw'",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R F R' F' Rw U R U' Rw'",
+            "R U2 R D R' U R D' R' U' R' U R U R' F R' F' Rw U R U' Rw'",
+            "F R U' R' U R U R2 F' R U R U' R' U' F R' F' Rw U R U' Rw'",
+            "F R U' R' U R U R2 F' R U R U' R' U2 F R' F' Rw U R U' Rw'",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U F R' F' Rw U R U' Rw'",
+            "R U2 R D R' U R D' R' U' R' U R U R' U F R' F' Rw U R U' Rw'",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U F R' F' Rw U R U' Rw'",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U F R' F' Rw U R U' Rw'",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U F R' F' Rw U R U' Rw'",
+            "R U2 R D R' U R D' R' U' R' U R U R' U' F R' F' Rw U R U' Rw'",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U' F R' F' Rw U R U' Rw'",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U' F R' F' Rw U R U' Rw'",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U' F R' F' Rw U R U' Rw'",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U' F R' F' Rw U R U' Rw'",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U2 F R' F' Rw U R U' Rw'",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U2 F R' F' Rw U R U' Rw'",
+            "R U2 R D R' U R D' R' U' R' U R U R' U2 F R' F' Rw U R U' Rw'",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U2 F R' F' Rw U R U' Rw'",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U2 F R' F' Rw U R U' Rw'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' F R' F' Rw U R U' Rw'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U2 F R' F' Rw U R U' Rw'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U' F R' F' Rw U R U' Rw'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U F R' F' Rw U R U' Rw'",
+            "R' U R' F R F' R U' R' F' U F R U R' U' R F R' F' Rw U R U' Rw'",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R F R' F' Rw U R U' Rw'",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x F R' F' Rw U R U' Rw'",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U F R' F' Rw U R U' Rw'",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U2 F R' F' Rw U R U' Rw'",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U' F R' F' Rw U R U' Rw'",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U F R' F' Rw U R U' Rw'",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U' F R' F' Rw U R U' Rw'",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U2 F R' F' Rw U R U' Rw'",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x U F R' F' Rw U R U' Rw'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U' R' U' R U' R' F R' F' Rw U R U' Rw'",
+        ],
+    ),
+    (
+        "L",
+        &[
+            "R' F' Rw U R U' Rw' F",
+            "M2 U M U2 M' U M2 R' F' Rw U R U' Rw' F",
+            "M2 U' M2 U2 M2 U' M2 R' F' Rw U R U' Rw' F",
+            "M2 U' M U2 M' U' M2 R' F' Rw U R U' Rw' F",
+            "M2 U M U2 M' U M2 U' R' F' Rw U R U' Rw' F",
+            "M2 U' M U2 M' U' M2 U2 R' F' Rw U R U' Rw' F",
+            "M2 U' M U2 M' U' M2 U' R' F' Rw U R U' Rw' F",
+            "M2 U' M U2 M' U' M2 U R' F' Rw U R U' Rw' F",
+            "M2 U M U2 M' U M2 U R' F' Rw U R U' Rw' F",
+            "M2 U M U2 M' U M2 U2 R' F' Rw U R U' Rw' F",
+            "M2 U2 M U' M2 U' M2 U' M R' F' Rw U R U' Rw' F",
+            "M2 U2 M U' M2 U' M2 U' M U R' F' Rw U R U' Rw' F",
+            "x R' U R' D2 R U' R' D2 R2 x' R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' R' F' Rw U R U' Rw' F",
+            "R' U2 R U2 R' F R U R' U' R' F' R F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' U' R' F' Rw U R U' Rw' F",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' R' F' Rw U R U' Rw' F",
+            "R U R2 F' R U R U' R' F R U' R2 F' Rw U R U' Rw' F",
+            "x R' U R' D2 R U' R' D2 R2 x' U2 R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' U R' F' Rw U R U' Rw' F",
+            "x R' U R' D2 R U' R' D2 R2 x' U' R' F' Rw U R U' Rw' F",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' F' Rw U R U' Rw' F",
+            "x R' U R' D2 R U' R' D2 R2 x' U R' F' Rw U R U' Rw' F",
+            "F R U' R' U R U R2 F' R U R U' R2 F' Rw U R U' Rw' F",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U F' Rw U R U' Rw' F",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U2 R' F' Rw U R U' Rw' F",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U R' F' Rw U R U' Rw' F",
+            "x U2 Rw' U' Rw U2 R' F R' F' R2 x' U' R' F' Rw U R U' Rw' F",
+            "R U2 R D R' U R D' R' U' R' U R U R2 F' Rw U R U' Rw' F",
+            "D R' U' R D' U R2 U R' U R U' R U' R F' Rw U R U' Rw' F",
+            "D' R U R' D U' R2 U' R U' R' U R' U R F' Rw U R U' Rw' F",
+            "D R2 U' R U' R U R' U R2 D' U R U' R2 F' Rw U R U' Rw' F",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U2 R' F' Rw U R U' Rw' F",
+            "R U R2 F' R U R U' R' F R U' R' U2 R' F' Rw U R U' Rw' F",
+            "R U R2 F' R U R U' R' F R U' R' U' R' F' Rw U R U' Rw' F",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U' R' F' Rw U R U' Rw' F",
+            "R' U2 R U2 R' F R U R' U' R' F' R2 U R' F' Rw U R U' Rw' F",
+            "R U R2 F' R U R U' R' F R U' R' U R' F' Rw U R U' Rw' F",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R2 F' Rw U R U' Rw' F",
+            "R' U R' F R F' R U' R' F' U F R U R' U' F' Rw U R U' Rw' F",
+            "F R U' R' U R U R2 F' R U R U' R' U2 R' F' Rw U R U' Rw' F",
+            "F R U' R' U R U R2 F' R U R U' R' U' R' F' Rw U R U' Rw' F",
+            "F R U' R' U R U R2 F' R U R U' R' U R' F' Rw U R U' Rw' F",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U F' Rw U R U' Rw' F",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U2 R' F' Rw U R U' Rw' F",
+            "R U2 R D R' U R D' R' U' R' U R U R' U2 R' F' Rw U R U' Rw' F",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U2 R' F' Rw U R U' Rw' F",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U2 R' F' Rw U R U' Rw' F",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U2 R' F' Rw U R U' Rw' F",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U R' F' Rw U R U' Rw' F",
+            "D R2 U' R U' R U R' U R2 D' U R U' R' U' R' F' Rw U R U' Rw' F",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U' R' F' Rw U R U' Rw' F",
+            "D R' U' R D' U R2 U R' U R U' R U' R2 U R' F' Rw U R U' Rw' F",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U' R' F' Rw U R U' Rw' F",
+            "R U2 R D R' U R D' R' U' R' U R U R' U' R' F' Rw U R U' Rw' F",
+            "R U2 R D R' U R D' R' U' R' U R U R' U R' F' Rw U R U' Rw' F",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U' R' F' Rw U R U' Rw' F",
+            "D' R2 U R' U R' U' R U' R2 D U' R' U R U R' F' Rw U R U' Rw' F",
+            "D' R U R' D U' R2 U' R U' R' U R' U R2 U R' F' Rw U R U' Rw' F",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U' R' F' Rw U R U' Rw' F",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U2 R' F' Rw U R U' Rw' F",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' R' F' Rw U R U' Rw' F",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R' U R' F' Rw U R U' Rw' F",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x R' F' Rw U R U' Rw' F",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U R' F' Rw U R U' Rw' F",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U2 R' F' Rw U R U' Rw' F",
+            "F R' F' R U R U' R' F R U' R' U R U R' F' U' R' F' Rw U R U' Rw' F",
+            "x' D R U R' D' R U' R' D R U' R' D' R U R' x U R' F' Rw U R U' Rw' F",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U2 R' F' Rw U R U' Rw' F",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U' R' F' Rw U R U' Rw' F",
+            "R' U' R U' R' U R U R2 F' R U R U' R' F U R U R' F' Rw U R U' Rw' F",
+            "R U R' U2 R U R2 F' R U R U' R' F R U' R' U' R U' R2 F' Rw U R U' Rw' F",
+        ],
+    ),
+];
+
+/// Setups of the 2x2 CLL cases by the shape of the U face, `O` having every corner oriented.
+const CLL: Shapes = &[
+    (
+        "H",
+        &[
+            "R U R' U R U' R' U R U2 R'",
+            "R U R2 F' R U R U' R' F U R U' R' U R U2 R'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U2 R' U R U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U' R' U R U2 R'",
+        ],
+    ),
+    (
+        "Pi",
+        &[
+            "R' U2 R2 U R2 U R2 U2 R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R2 U R2 U R2 U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R2 U R2 U R2 U2 R'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R2 U R2 U R2 U2 R'",
+        ],
+    ),
+    (
+        "U",
+        &[
+            "R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R' D' R U2 R' D R2",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R' D' R U2 R' D R2",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R' D' R U2 R' D R2",
+            "R' U R' F R F' R U' R' F' U F R U R' U R' D' R U2 R' D R2",
+        ],
+    ),
+    (
+        "T",
+        &[
+            "F R' F' R U R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' F R' F' R U R U' R'",
+            "x R' U R' D2 R U' R' D2 R2 x' F R' F' R U R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' U' F R' F' R U R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 F R' F' R U R U' R'",
+            "F R' F' R U R U' R' F R U' R' U R U R2 F' R U R U' R'",
+        ],
+    ),
+    (
+        "L",
+        &[
+            "R' F' R U R U' R' F",
+            "x R' U R' D2 R U' R' D2 R2 x' R' F' R U R U' R' F",
+            "x R2 D2 R U R' D2 R U' R x' R' F' R U R U' R' F",
+            "x R2 D2 R U R' D2 R U' R x' U' R' F' R U R U' R' F",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' F' R U R U' R' F",
+            "R' U R' F R F' R U' R' F' U F R U R' U' F' R U R U' R' F",
+        ],
+    ),
+    (
+        "Antisune",
+        &[
+            "R U R' U R U2 R'",
+            "R U R2 F' R U R U' R' F U R U2 R'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U R'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U2 R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U R' U R U2 R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U R' U R U2 R'",
+        ],
+    ),
+    (
+        "Sune",
+        &[
+            "R U2 R' U' R U' R'",
+            "R U R2 F' R U R U' R' F R U R' U' R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' R U2 R' U' R U' R'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U2 R' U' R U' R'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U2 R' U' R U' R'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U' R' U' R U' R'",
+        ],
+    ),
+    (
+        "O",
+        &[
+            "x R2 D2 R U R' D2 R U' R x'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U R'",
+        ],
+    ),
+];
+
+/// Setups of the 2x2 EG-1 cases: CLL with the right corners of the D layer swapped.
+const EG_1: Shapes = &[
+    (
+        "H",
+        &[
+            "R U R' U R U' R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "R U R2 F' R U R U' R' F U R U' R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U2 R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U' R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "Pi",
+        &[
+            "R' U2 R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R2 U R2 U R2 U2 D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "U",
+        &[
+            "R' U2 R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R' D' R U2 R' D R' D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "T",
+        &[
+            "F R' F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' F R' F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' F R' F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U' F R' F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 F R' F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "F R' F' R U R U' R' F R U' R' U R U R2 F' R U R U' D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "L",
+        &[
+            "R' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U' F' R U R U' R' F R D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "Antisune",
+        &[
+            "R U R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "R U R2 F' R U R U' R' F U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U R' U R U2 D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+    (
+        "Sune",
+        &[
+            "R U2 R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "R U R2 F' R U R U' R' F R U R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' R U2 R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U2 R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U2 R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U' R' U' R U' D R' D' R' B R2 D' R' D' R D R' B'",
+        ],
+    ),
+];
+
+/// Setups of the 2x2 EG-2 cases: CLL with diagonal corners of the D layer swapped.
+const EG_2: Shapes = &[
+    (
+        "H",
+        &[
+            "R U R' U R U' R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R U R2 F' R U R U' R' F U R U' R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U2 R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U' R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "Pi",
+        &[
+            "R' U2 R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R2 U R2 U R2 U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "U",
+        &[
+            "R' U2 R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R' U2 R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' U2 R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' U2 R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' U2 R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U R' D' R U2 R' D R2 B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "T",
+        &[
+            "F R' F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' F R' F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' F R' F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U' F R' F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 F R' F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "F R' F' R U R U' R' F R U' R' U R U R2 F' R U R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "L",
+        &[
+            "R' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U' R' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R' U R' F R F' R U' R' F' U F R U R' U' F' R U R U' R' F B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "Antisune",
+        &[
+            "R U R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R U R2 F' R U R U' R' F U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R U R' U2 R U R2 F' R U R U' R' F R U R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R U R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U R' U R U2 R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+    (
+        "Sune",
+        &[
+            "R U2 R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R U R2 F' R U R U' R' F R U R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' R U2 R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R' U R' D2 R U' R' D2 R2 x' R U2 R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "x R2 D2 R U R' D2 R U' R x' U2 R U2 R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+            "R2 D R2 U' R2 D' U R' D R' D' R U' R' U' R' U' R U' R' B R D' R' D' R D R' B' R D R' D' R' B R B'",
+        ],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::Face;
+    use crate::utils::color::Color;
+    use std::collections::HashSet;
+
+    const AUF: [&str; 4] = ["", "U", "U2", "U'"];
+
+    /// Stickers shown by the mask of the case after `auf`, with the side colors of the last
+    /// layer pieces turned `turns` times around U, as when starting from another AUF.
+    fn stickers<T>(case: &Case, auf: &str, turns: usize) -> Vec<Option<usize>>
+    where
+        usize: From<T>,
+        T: Default,
+    {
+        let sides = [Face::F, Face::R, Face::B, Face::L];
+        let turned = |face: Face| match sides.iter().position(|f| *f == face) {
+            Some(i) => sides[(i + turns) % 4],
+            None => face,
+        };
+
+        let mut cube = Cube::<T>::new();
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.try_apply_scramble(&format!("{} {auf}", case.setup))
+            .unwrap();
+
+        let visible = case.mask().map(|m| m.visible(cube.state.dim().1));
+        let origins = cube.orientation().unwrap();
+        cube.state
+            .iter()
+            .zip(origins)
+            .map(|(&face, origin)| {
+                let shown = visible
+                    .as_ref()
+                    .is_none_or(|v| v[[origin.face as usize, origin.row, origin.col]]);
+                let last_layer =
+                    origin.face == Face::U || origin.face != Face::D && origin.row == 0;
+                let face = if last_layer { turned(face) } else { face };
+                shown.then_some(face as usize)
+            })
+            .collect()
+    }
+
+    /// The same for every case that only differs by AUF.
+    fn recognition(case: &Case) -> Vec<Option<usize>> {
+        AUF.iter()
+            .flat_map(|auf| {
+                (0..4).map(move |turns| match case.group.size() {
+                    2 => stickers::<TwoByTwo>(case, auf, turns),
+                    _ => stickers::<ThreeByThree>(case, auf, turns),
+                })
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn catalogue() {
+        let count = |group| cases().iter().filter(|c| c.group == group).count();
+        assert_eq!(count(Group::F2l), 41);
+        assert_eq!(count(Group::Oll), 57);
+        assert_eq!(count(Group::Pll), 21);
+        assert_eq!(count(Group::Coll), 42);
+        assert_eq!(count(Group::Zbll), 216);
+        assert_eq!(count(Group::Cll), 42);
+        assert_eq!(count(Group::Eg1), 40);
+        assert_eq!(count(Group::Eg2), 40);
+
+        let names: HashSet<&str> = cases().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names.len(), cases().len());
+
+        assert_eq!(case(" pll  ua").unwrap().name, "PLL Ua");
+        assert_eq!(case("OLL 27").unwrap().setup, "R U2 R' U' R U' R'");
+        assert_eq!(case("f2l r u' r'").unwrap().setup, "R U' R'");
+        let name = format!("ZBLL T {}", ZBLL[1].1[0]);
+        assert_eq!(case(&name).unwrap().group, Group::Zbll);
+        let name = format!("EG-2 Sune {}", EG_2[6].1[5]);
+        assert_eq!(case(&name).unwrap().group.size(), 2);
+        assert!(case("F2L 1").is_none());
+        assert!(case("OLL 58").is_none());
+        assert!(case("PLL").is_none());
+    }
+
+    #[test]
+    fn setups() {
+        for case in cases() {
+            let (state, solved) = match case.group.size() {
+                2 => {
+                    let mut cube = Cube::<TwoByTwo>::new();
+                    cube.try_apply_scramble(case.setup).unwrap();
+                    (cube.state, Cube::<TwoByTwo>::new().state)
+                }
+                _ => {
+                    let mut cube = Cube::<ThreeByThree>::new();
+                    cube.try_apply_scramble(case.setup).unwrap();
+                    (cube.state, Cube::<ThreeByThree>::new().state)
+                }
+            };
+            let size = state.dim().1;
+
+            // Everything outside the pieces the case is about is left solved.
+            let kept = match case.group {
+                Group::F2l => Mask::Cross.visible(size),
+                Group::Eg1 | Group::Eg2 => Mask::Oll.visible(size).map(|_| false),
+                Group::Cll => Mask::FirstLayer.visible(size),
+                _ => Mask::F2l.visible(size),
+            };
+            for ((position, face), kept) in state.indexed_iter().zip(kept) {
+                assert!(!kept || *face == solved[position], "{}", case.name);
+            }
+            assert_ne!(state, solved, "{}", case.name);
+        }
+    }
+
+    #[test]
+    fn distinct_cases() {
+        let mut seen = HashSet::new();
+        for case in cases() {
+            assert!(
+                seen.insert((case.group, recognition(case))),
+                "{} is the same as an earlier case",
+                case.name
+            );
+        }
+    }
+
+    /// Moves undoing the setup, the algorithm of the case.
+    fn algorithm(case: &Case) -> String {
+        let moves = case.setup.split_whitespace().rev();
+        let inverse = moves.map(|mv| match mv.strip_suffix('\'') {
+            Some(mv) => mv.to_string(),
+            None if mv.ends_with('2') => mv.to_string(),
+            None => format!("{mv}'"),
+        });
+        inverse.collect::<Vec<_>>().join(" ")
+    }
+
+    fn scrambled(moves: &str) -> Cube<ThreeByThree> {
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.try_apply_scramble(moves).unwrap();
+        cube
+    }
+
+    #[test]
+    fn recognising_cases() {
+        let oll_solved = |cube: &Cube<ThreeByThree>| {
+            let face = cube.state.slice(ndarray::s![Face::U as usize, .., ..]);
+            face.iter().all(|f| *f == Face::U)
+        };
+
+        for case in cases().iter().filter(|c| c.group == Group::Oll) {
+            for auf in Auf::ALL {
+                for (rotation, undo) in [("", ""), ("y", "y'"), ("y2", "y2")] {
+                    let moves = format!("{} {} {rotation}", case.setup, auf.inverse());
+                    let Some(LastLayer::Oll {
+                        case: found,
+                        pre_auf,
+                    }) = recognise(&scrambled(&moves))
+                    else {
+                        panic!("{} not recognised", case.name);
+                    };
+                    assert_eq!(found, case);
+
+                    let solve = format!("{moves} {undo} {pre_auf} {}", algorithm(case));
+                    assert!(oll_solved(&scrambled(&solve)), "{}", case.name);
+                }
+            }
+        }
+
+        for case in cases().iter().filter(|c| c.group == Group::Pll) {
+            for (pre, post) in Auf::ALL
+                .into_iter()
+                .zip([Auf::U2, Auf::None, Auf::U, Auf::UPrime])
+            {
+                let moves = format!("{post} {} {}", case.setup, pre.inverse());
+                let Some(LastLayer::Pll {
+                    case: found,
+                    pre_auf,
+                    post_auf,
+                }) = recognise(&scrambled(&moves))
+                else {
+                    panic!("{} not recognised", case.name);
+                };
+                assert_eq!(found, case);
+
+                let solve = format!("{moves} {pre_auf} {} {post_auf}", algorithm(case));
+                assert_eq!(scrambled(&solve).state, Cube::<ThreeByThree>::new().state);
+            }
+        }
+    }
+
+    #[test]
+    fn recognising_aufs() {
+        let t = case("PLL T").unwrap();
+        assert_eq!(
+            recognise(&scrambled(&format!("{} U", t.setup))),
+            Some(LastLayer::Pll {
+                case: t,
+                pre_auf: Auf::UPrime,
+                post_auf: Auf::None
+            })
+        );
+        assert_eq!(
+            recognise(&scrambled("R U R' U R U2 R' U y'")),
+            Some(LastLayer::Oll {
+                case: case("OLL 26").unwrap(),
+                pre_auf: Auf::UPrime
+            })
+        );
+        assert_eq!(
+            recognise(&scrambled("U2 y")),
+            Some(LastLayer::Solved { auf: Auf::U2 })
+        );
+        assert_eq!(
+            recognise(&scrambled("")),
+            Some(LastLayer::Solved { auf: Auf::None })
+        );
+
+        assert_eq!(recognise(&scrambled("R U R'")), None);
+        assert_eq!(recognise(&scrambled("x")), None);
+        assert_eq!(Auf::UPrime.to_string(), "U'");
+    }
+
+    #[test]
+    fn drawing() {
+        let grey = Color::grey().to_string();
+
+        let svg = case("PLL T").unwrap().draw().to_string();
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert_eq!(svg.matches(&grey).count(), 0);
+
+        let svg = case("OLL 27").unwrap().draw().to_string();
+        assert_eq!(svg.matches("<rect").count(), 21);
+        assert_eq!(svg.matches(&grey).count(), 12);
+
+        let svg = case("COLL Sune R U2 R' U' R U' R'")
+            .unwrap()
+            .draw()
+            .to_string();
+        assert_eq!(svg.matches(&grey).count(), 4);
+
+        let svg = case("F2L R U' R U2 F R2 F' U2 R2")
+            .unwrap()
+            .draw()
+            .to_string();
+        assert_eq!(svg.matches("<rect").count(), 54);
+        assert_eq!(svg.matches(&grey).count(), 21);
+
+        let name = format!("EG-1 H {}", EG_1[0].1[0]);
+        let svg = case(&name).unwrap().draw().to_string();
+        assert_eq!(svg.matches("<rect").count(), 24);
+        assert_eq!(svg.matches(&grey).count(), 0);
+    }
+}
@@ -1,21 +1,37 @@
-use crate::puzzles::{ColorSchemes, Puzzle};
+use crate::puzzles::bld::LetteringScheme;
+use crate::puzzles::metrics::MoveCount;
+use crate::puzzles::net::{self, face_colors, move_table, Net, Painter};
+use crate::puzzles::render::{body, document, escape, mark_face, palette, patterns, Stickers};
+use crate::puzzles::{state_json, ColorSchemes, Puzzle, RenderOptions, ScrambleError, StateError};
 use crate::utils::color::Color;
-use crate::utils::maths::rotate_2d_matrix;
 use lazy_static::lazy_static;
-use ndarray::{s, Array1, Array3};
+use ndarray::{s, Array3};
 use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
-use svg::node::element::{Group, Rectangle, SVG};
-use svg::{Document, Node};
+use svg::node::element::{Definitions, Group, Line, Marker, Path, Text, SVG};
+use svg::Node;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "CubeData",
+        bound(deserialize = "usize: From<T>, T: Default")
+    )
+)]
 pub struct Cube<T> {
     pub state: Array3<Face>,
     size: usize,
     color_scheme: HashMap<Face, Color>,
-    cubie_size: usize,
-    gap: usize,
+    mask: Option<Array3<bool>>,
+    orientation: Option<Array3<Sticker>>,
+    orientation_marks: OrientationMarks,
+    lettering: Option<LetteringScheme>,
+    letter_centers: bool,
+    cubie_size: f64,
+    gap: f64,
     _marker: PhantomData<T>,
 }
 
@@ -32,16 +48,38 @@ pub struct SixBySix {}
 #[derive(Debug, Default)]
 pub struct SevenBySeven {}
 
-#[derive(Debug)]
-struct Move {
-    face: Face,
-    dir: Direction,
-    depth: usize,
-    wide: bool,
+/// A move in cube notation, as read by [`parse_scramble`].
+#[derive(Debug, Clone, Copy)]
+pub struct Move {
+    pub(crate) face: Face,
+    pub(crate) dir: Direction,
+    pub(crate) layers: Layers,
+    pub(crate) wide: bool,
+}
+
+/// The layers a move turns, as written: some are only known once the puzzle is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layers {
+    /// Every layer, for the rotations `x`, `y` and `z`.
+    Whole,
+    /// The middle layer of odd puzzles, for the slices `M`, `E` and `S`.
+    Middle,
+    /// The layer at this depth from the face, and the ones above it for wide moves.
+    Depth(usize),
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum Direction {
+/// A move made on a puzzle, with the layers it turns found by [`Move::within`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LayerTurn {
+    pub(crate) face: Face,
+    pub(crate) dir: Direction,
+    /// Depth of the last layer turned, 0 for the face.
+    pub(crate) depth: usize,
+    pub(crate) wide: bool,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Direction {
     None,
     Clockwise,
     Half,
@@ -92,6 +130,7 @@ impl From<SevenBySeven> for usize {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Face {
     R,
     U,
@@ -135,6 +174,30 @@ impl fmt::Display for Face {
     }
 }
 
+impl Face {
+    fn from_facelet(c: char) -> Option<Face> {
+        match c {
+            'R' | 'U' | 'F' | 'L' | 'D' | 'B' => Some(Face::from(c)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn facelet(self) -> char {
+        match self {
+            Face::R => 'R',
+            Face::U => 'U',
+            Face::F => 'F',
+            Face::L => 'L',
+            Face::D => 'D',
+            Face::B => 'B',
+        }
+    }
+
+    pub(crate) fn opposite(self) -> Face {
+        Face::from((self as usize + 3) % 6)
+    }
+}
+
 impl PartialEq<usize> for Face {
     fn eq(&self, other: &usize) -> bool {
         self == &Face::from(*other)
@@ -158,6 +221,493 @@ lazy_static! {
     ]);
 }
 
+impl Move {
+    /// The move on a puzzle with `layers` layers along its axis, `None` if it does not fit.
+    pub(crate) fn within(self, layers: usize) -> Option<LayerTurn> {
+        let (depth, wide) = match self.layers {
+            Layers::Whole => (layers - 1, true),
+            Layers::Middle => ((layers % 2 == 1).then_some(layers / 2)?, false),
+            Layers::Depth(depth) => ((depth < layers).then_some(depth)?, self.wide),
+        };
+        Some(LayerTurn {
+            face: self.face,
+            dir: self.dir,
+            depth,
+            wide,
+        })
+    }
+}
+
+impl LayerTurn {
+    /// Whether the move turns all of the `layers` layers along its axis, like `x` or `3Rw` on
+    /// a 3x3.
+    pub(crate) fn is_rotation(self, layers: usize) -> bool {
+        self.wide && self.depth == layers - 1
+    }
+
+    /// Whether the move turns inner layers only of the `layers` along its axis, like `M` or
+    /// `2R`.
+    pub(crate) fn is_slice(self, layers: usize) -> bool {
+        !self.wide && self.depth > 0 && self.depth < layers - 1
+    }
+}
+
+/// Reads a move in cube notation: the number of layers, the face, `w` for wide moves, then
+/// the number of quarter turns and `'` for counterclockwise ones. Rotations `x`, `y`, `z` and
+/// slices `M`, `E`, `S` are turned like `R`, `U`, `F` and `L`, `D`, `F`. `None` if it is not a
+/// move.
+pub(crate) fn parse_move(mv: &str) -> Option<Move> {
+    let a = mv.find(|c: char| !c.is_ascii_digit())?;
+    let (layers, rest) = mv.split_at(a);
+
+    let mut chars = rest.chars();
+    let f = chars.next()?;
+    let (face, span) = match f {
+        'x' => (Face::R, Some(Layers::Whole)),
+        'y' => (Face::U, Some(Layers::Whole)),
+        'z' => (Face::F, Some(Layers::Whole)),
+        'M' => (Face::L, Some(Layers::Middle)),
+        'E' => (Face::D, Some(Layers::Middle)),
+        'S' => (Face::F, Some(Layers::Middle)),
+        _ => (Face::from_facelet(f.to_ascii_uppercase())?, None),
+    };
+
+    let rest = chars.as_str();
+    let (wide, rest) = match rest.strip_prefix('w') {
+        // Rotations and slices cannot be wide.
+        Some(rest) if span.is_none() => (true, rest),
+        _ => (false, rest),
+    };
+    if wide && f.is_lowercase() {
+        // Lowercase faces are already wide in most notations.
+        return None;
+    }
+    if span.is_some() && !layers.is_empty() {
+        return None;
+    }
+
+    let (amount, prime) = match rest.strip_suffix('\'') {
+        Some(amount) => (amount, true),
+        None => (rest, false),
+    };
+    if !amount.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let quarter_turns = if amount.is_empty() {
+        1
+    } else {
+        // Only the last two digits matter for a number of quarter turns.
+        amount[amount.len().saturating_sub(2)..]
+            .parse::<usize>()
+            .ok()?
+            % 4
+    };
+
+    let layers = if layers.is_empty() {
+        if wide {
+            2
+        } else {
+            1
+        }
+    } else {
+        layers.parse::<usize>().ok()?
+    };
+    if layers == 0 {
+        return None;
+    }
+
+    let dir = if prime {
+        (4 - quarter_turns) % 4
+    } else {
+        quarter_turns
+    };
+    Some(Move {
+        face,
+        dir: Direction::from(dir),
+        layers: span.unwrap_or(Layers::Depth(layers - 1)),
+        wide,
+    })
+}
+
+/// Reads every move of a scramble in cube notation.
+pub fn parse_scramble(scramble: &str) -> Result<Vec<Move>, ScrambleError> {
+    scramble
+        .split_ascii_whitespace()
+        .enumerate()
+        .map(|(index, mv)| {
+            parse_move(mv).ok_or_else(|| ScrambleError::Notation {
+                index,
+                found: mv.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Reads every move of a scramble in cube notation on a puzzle with `layers(face)` layers
+/// along the axis of each face.
+pub(crate) fn parse_scramble_within(
+    scramble: &str,
+    layers: impl Fn(Face) -> usize,
+) -> Result<Vec<LayerTurn>, ScrambleError> {
+    parse_scramble(scramble)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, mv)| {
+            let layers = layers(mv.face);
+            mv.within(layers).ok_or_else(|| ScrambleError::Layer {
+                index,
+                found: nth_move(scramble, index),
+                layers,
+            })
+        })
+        .collect()
+}
+
+/// The move at `index` in a scramble, as written.
+pub(crate) fn nth_move(scramble: &str, index: usize) -> String {
+    scramble
+        .split_ascii_whitespace()
+        .nth(index)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Order of the faces in a facelet string, as used by Kociemba's solver.
+const FACELET_ORDER: [Face; 6] = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B];
+
+/// A sticker position on the cube: face, row and column.
+pub(crate) type Position = (Face, usize, usize);
+
+/// Corner positions, first sticker on U or D then clockwise. Rows and columns are 0 for the
+/// first, 1 for the middle and 2 for the last of a face, see [`scale`].
+pub(crate) const CORNERS: [(&str, [Position; 3]); 8] = [
+    ("URF", [(Face::U, 2, 2), (Face::R, 0, 0), (Face::F, 0, 2)]),
+    ("UFL", [(Face::U, 2, 0), (Face::F, 0, 0), (Face::L, 0, 2)]),
+    ("ULB", [(Face::U, 0, 0), (Face::L, 0, 0), (Face::B, 0, 2)]),
+    ("UBR", [(Face::U, 0, 2), (Face::B, 0, 0), (Face::R, 0, 2)]),
+    ("DFR", [(Face::D, 0, 2), (Face::F, 2, 2), (Face::R, 2, 0)]),
+    ("DLF", [(Face::D, 0, 0), (Face::L, 2, 2), (Face::F, 2, 0)]),
+    ("DBL", [(Face::D, 2, 0), (Face::B, 2, 2), (Face::L, 2, 0)]),
+    ("DRB", [(Face::D, 2, 2), (Face::R, 2, 2), (Face::B, 2, 0)]),
+];
+
+/// Middle edge positions, first sticker on U or D, or on F or B for the middle layer.
+pub(crate) const EDGES: [(&str, [Position; 2]); 12] = [
+    ("UR", [(Face::U, 1, 2), (Face::R, 0, 1)]),
+    ("UF", [(Face::U, 2, 1), (Face::F, 0, 1)]),
+    ("UL", [(Face::U, 1, 0), (Face::L, 0, 1)]),
+    ("UB", [(Face::U, 0, 1), (Face::B, 0, 1)]),
+    ("DR", [(Face::D, 1, 2), (Face::R, 2, 1)]),
+    ("DF", [(Face::D, 0, 1), (Face::F, 2, 1)]),
+    ("DL", [(Face::D, 1, 0), (Face::L, 2, 1)]),
+    ("DB", [(Face::D, 2, 1), (Face::B, 2, 1)]),
+    ("FR", [(Face::F, 1, 2), (Face::R, 1, 0)]),
+    ("FL", [(Face::F, 1, 0), (Face::L, 1, 2)]),
+    ("BL", [(Face::B, 1, 2), (Face::L, 1, 0)]),
+    ("BR", [(Face::B, 1, 0), (Face::R, 1, 2)]),
+];
+
+/// Turns a position from the tables above into one on a cube of the given size.
+pub(crate) fn scale((face, row, col): Position, size: usize) -> Position {
+    let scale = |x| match x {
+        0 => 0,
+        1 => size / 2,
+        _ => size - 1,
+    };
+    (face, scale(row), scale(col))
+}
+
+/// Checks that a state can be reached by turning a cube: the centers must be in a valid
+/// orientation and every corner must be a real piece, with no single twisted corner. On the
+/// 3x3, edges are checked the same way along with permutation parity.
+fn check_state(state: &Array3<Face>) -> Result<(), StateError> {
+    let size = state.dim().1;
+
+    // Colors are relabelled after the centers, so that a cube turned with slice moves is
+    // checked as if it had been rotated back.
+    let mut relabel = [Face::R, Face::U, Face::F, Face::L, Face::D, Face::B];
+    if size % 2 == 1 {
+        let center = |f: Face| state[[f as usize, size / 2, size / 2]];
+
+        for i in 0..6 {
+            let face = Face::from(i);
+            if center(face.opposite()) != center(face).opposite() {
+                return Err(StateError::Centers);
+            }
+            relabel[center(face) as usize] = face;
+        }
+
+        let frame = [center(Face::U), center(Face::R), center(Face::F)];
+        let valid = CORNERS.iter().any(|(_, corner)| {
+            let colors = corner.map(|(f, _, _)| f);
+            (0..3).any(|t| (0..3).all(|i| colors[(t + i) % 3] == frame[i]))
+        });
+        if !valid {
+            return Err(StateError::Centers);
+        }
+    }
+
+    let color = |p: Position| {
+        let (f, row, col) = scale(p, size);
+        relabel[state[[f as usize, row, col]] as usize]
+    };
+
+    let mut corners = [None; 8];
+    let mut twist = 0;
+
+    for (i, (position, stickers)) in CORNERS.iter().enumerate() {
+        let colors = stickers.map(color);
+
+        let t = colors
+            .iter()
+            .position(|c| *c == Face::U || *c == Face::D)
+            .ok_or(StateError::Corner { position })?;
+
+        let piece = CORNERS
+            .iter()
+            .position(|(_, corner)| (0..3).all(|k| corner[k].0 == colors[(t + k) % 3]))
+            .ok_or(StateError::Corner { position })?;
+
+        if corners.contains(&Some(piece)) {
+            return Err(StateError::DuplicateCorner { position });
+        }
+        corners[i] = Some(piece);
+        twist += t;
+    }
+
+    if twist % 3 != 0 {
+        return Err(StateError::CornerTwist);
+    }
+
+    if size != 3 {
+        return Ok(());
+    }
+
+    let mut edges = [None; 12];
+    let mut flip = 0;
+
+    for (i, (position, stickers)) in EDGES.iter().enumerate() {
+        let colors = stickers.map(color);
+
+        let (piece, f) = EDGES
+            .iter()
+            .enumerate()
+            .find_map(|(j, (_, edge))| {
+                let solved = edge.map(|(f, _, _)| f);
+                if solved == colors {
+                    Some((j, 0))
+                } else if solved == [colors[1], colors[0]] {
+                    Some((j, 1))
+                } else {
+                    None
+                }
+            })
+            .ok_or(StateError::Edge { position })?;
+
+        if edges.contains(&Some(piece)) {
+            return Err(StateError::DuplicateEdge { position });
+        }
+        edges[i] = Some(piece);
+        flip += f;
+    }
+
+    if flip % 2 != 0 {
+        return Err(StateError::EdgeFlip);
+    }
+
+    let corners = corners.map(Option::unwrap);
+    let edges = edges.map(Option::unwrap);
+    if permutation_parity(&corners) != permutation_parity(&edges) {
+        return Err(StateError::Parity);
+    }
+
+    Ok(())
+}
+
+/// Whether a permutation, given as the image of each index, is odd.
+pub(crate) fn permutation_parity(permutation: &[usize]) -> bool {
+    let mut visited = vec![false; permutation.len()];
+    let mut odd = false;
+
+    for start in 0..permutation.len() {
+        let mut i = start;
+        while !visited[i] {
+            visited[i] = true;
+            i = permutation[i];
+            if i != start {
+                odd = !odd;
+            }
+        }
+    }
+
+    odd
+}
+
+/// Where a sticker started from and how many quarter turns clockwise it has been rotated in
+/// the net since, for supercubes and picture cubes where center orientation matters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sticker {
+    pub face: Face,
+    pub row: usize,
+    pub col: usize,
+    pub rotation: u8,
+}
+
+/// Stickers drawn with a notch showing their orientation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OrientationMarks {
+    Hidden,
+    /// Stickers away from the edges of their face.
+    Centers,
+    All,
+}
+
+/// Stickers to show on training views, every other sticker is drawn in grey.
+///
+/// Presets assume the last layer is on U and the cross on D.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mask {
+    /// D face cross, side centers and the edges around them.
+    Cross,
+    /// Everything but the U layer.
+    F2l,
+    /// U face only.
+    Oll,
+    /// The whole U layer.
+    Pll,
+    /// U face and the U layer corners.
+    Coll,
+    /// The whole U layer, ZBLL cases are recognised from all of it.
+    Zbll,
+    /// 2x2 first layer, the F2L equivalent: the whole D layer.
+    FirstLayer,
+    /// 2x2 CLL: the whole U layer.
+    Cll,
+    /// Per-sticker mask with the same shape as `Cube::state`, `true` meaning visible.
+    Custom(Array3<bool>),
+}
+
+impl Mask {
+    pub fn visible(&self, size: usize) -> Array3<bool> {
+        if let Mask::Custom(mask) = self {
+            return mask.clone();
+        }
+
+        let last = size - 1;
+
+        Array3::from_shape_fn((6, size, size), |(f, row, col)| {
+            let face = Face::from(f);
+            let side = face != Face::U && face != Face::D;
+            let corner = (row == 0 || row == last) && (col == 0 || col == last);
+
+            let top_layer = face == Face::U || side && row == 0;
+            let bottom_layer = face == Face::D || side && row == last;
+
+            match self {
+                Mask::Cross => match face {
+                    Face::D => !corner,
+                    Face::U => false,
+                    _ => row != 0 && col != 0 && col != last,
+                },
+                Mask::F2l => !top_layer,
+                Mask::Oll => face == Face::U,
+                Mask::Pll | Mask::Zbll | Mask::Cll => top_layer,
+                Mask::Coll => face == Face::U || top_layer && corner,
+                Mask::FirstLayer => bottom_layer,
+                Mask::Custom(_) => unreachable!(),
+            }
+        })
+    }
+}
+
+// Derived, `Clone` would only be implemented for sizes that are `Clone` themselves.
+impl<T> Clone for Cube<T> {
+    fn clone(&self) -> Self {
+        Cube {
+            state: self.state.clone(),
+            size: self.size,
+            color_scheme: self.color_scheme.clone(),
+            mask: self.mask.clone(),
+            orientation: self.orientation.clone(),
+            orientation_marks: self.orientation_marks,
+            lettering: self.lettering.clone(),
+            letter_centers: self.letter_centers,
+            cubie_size: self.cubie_size,
+            gap: self.gap,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A cube as it is deserialized, before checking that it is one of its size.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CubeData {
+    state: Array3<Face>,
+    size: usize,
+    color_scheme: HashMap<Face, Color>,
+    mask: Option<Array3<bool>>,
+    orientation: Option<Array3<Sticker>>,
+    orientation_marks: OrientationMarks,
+    lettering: Option<LetteringScheme>,
+    letter_centers: bool,
+    cubie_size: f64,
+    gap: f64,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<CubeData> for Cube<T>
+where
+    usize: From<T>,
+    T: Default,
+{
+    type Error = StateError;
+
+    fn try_from(data: CubeData) -> Result<Self, Self::Error> {
+        let mut cube = Cube::<T>::new();
+        if data.size != cube.size {
+            return Err(StateError::Size {
+                expected: cube.size,
+                found: data.size,
+            });
+        }
+        if data.state.dim() != cube.state.dim() {
+            return Err(StateError::Shape {
+                expected: cube.state.dim(),
+                found: data.state.dim(),
+            });
+        }
+        check_stickers(&data.state)?;
+        cube.state = data.state;
+
+        cube.set_mask(data.mask.map(Mask::Custom))?;
+        if let Some(orientation) = &data.orientation {
+            let size = cube.size;
+            let valid = orientation.dim() == cube.state.dim()
+                && orientation
+                    .iter()
+                    .all(|s| s.row < size && s.col < size && s.rotation < 4);
+            if !valid {
+                return Err(StateError::Orientation);
+            }
+        }
+
+        Ok(Cube {
+            color_scheme: data.color_scheme,
+            orientation: data.orientation,
+            orientation_marks: data.orientation_marks,
+            lettering: data.lettering,
+            letter_centers: data.letter_centers,
+            cubie_size: data.cubie_size,
+            gap: data.gap,
+            ..cube
+        })
+    }
+}
+
 impl<T> Puzzle for Cube<T>
 where
     usize: From<T>,
@@ -168,177 +718,665 @@ where
 
         Cube {
             size,
-            cubie_size: 10,
-            gap: 2,
+            cubie_size: 10.,
+            gap: 2.,
             state: Array3::<Face>::from_shape_fn((6, size, size), |(i, _, _)| Face::from(i)),
             color_scheme: DEFAULT_COLOR_SCHEME.clone(),
+            mask: None,
+            orientation: None,
+            orientation_marks: OrientationMarks::Hidden,
+            lettering: None,
+            letter_centers: false,
             _marker: Default::default(),
         }
     }
 
-    fn apply_scramble(&mut self, scramble: &str) {
-        let moves = scramble
-            .split_ascii_whitespace()
-            .map(Cube::<T>::parse_moves);
-        moves.for_each(|mut mv| self.apply_move(&mut mv));
+    fn try_apply_scramble(&mut self, scramble: &str) -> Result<(), ScrambleError> {
+        let moves = parse_scramble_within(scramble, |_| self.size)?;
+
+        moves.iter().for_each(|mv| self.apply_move(mv));
+        Ok(())
+    }
+
+    fn move_count(&self, alg: &str) -> Result<MoveCount, ScrambleError> {
+        MoveCount::cube(alg, self.size)
     }
 
     fn get_default_color_scheme(&self) -> ColorSchemes {
         ColorSchemes::Cube(DEFAULT_COLOR_SCHEME.clone())
     }
 
-    fn draw(&self) -> SVG {
-        let (width, height) = self.get_preferred_size();
+    fn to_facelets(&self) -> String {
+        FACELET_ORDER
+            .iter()
+            .flat_map(|f| self.state.slice(s![*f as usize, .., ..]).to_owned())
+            .map(Face::facelet)
+            .collect()
+    }
 
-        let mut svg = Document::new()
-            .set("viewBox", (0, 0, width, height))
-            .set("height", "100%")
-            .set("width", "100%");
+    fn to_json(&self) -> String {
+        let faces: Vec<(String, Vec<String>)> = FACELET_ORDER
+            .iter()
+            .map(|f| {
+                let stickers = self.state.slice(s![*f as usize, .., ..]);
+                (
+                    f.to_string(),
+                    stickers.iter().map(Face::to_string).collect(),
+                )
+            })
+            .collect();
 
-        self.draw_cube(&mut svg);
+        state_json("cube", Some(&self.size.to_string()), &faces)
+    }
+
+    fn draw_with(&self, options: &RenderOptions) -> SVG {
+        let (width, height) = self.get_preferred_size(options);
+
+        let mut svg = document(width, height, options, &self.title(), || {
+            self.describe(&FACELET_ORDER, self.size)
+        });
+        self.draw_cube(&mut svg, options);
         svg
     }
 }
 
+impl<T> Cube<T>
+where
+    usize: From<T>,
+    T: Default,
+{
+    /// Builds a cube from a facelet string: one face letter per sticker, faces in `URFDLB`
+    /// order as in Kociemba's solver, each read row by row as drawn in the net.
+    ///
+    /// The state must be reachable, see [`StateError`] for what is checked.
+    pub fn from_facelets(facelets: &str) -> Result<Self, StateError> {
+        let mut cube = Cube::<T>::new();
+        let size = cube.size;
+        let stickers = size * size;
+
+        let found = facelets.chars().count();
+        if found != 6 * stickers {
+            return Err(StateError::Length {
+                expected: 6 * stickers,
+                found,
+            });
+        }
+
+        for (index, c) in facelets.chars().enumerate() {
+            let face = Face::from_facelet(c).ok_or(StateError::Facelet { index, found: c })?;
+            let f = FACELET_ORDER[index / stickers] as usize;
+            cube.state[[f, index % stickers / size, index % size]] = face;
+        }
+
+        check_stickers(&cube.state)?;
+        Ok(cube)
+    }
+}
+
+/// Checks that every color is on as many stickers as a face has, and that the state can be
+/// reached.
+fn check_stickers(state: &Array3<Face>) -> Result<(), StateError> {
+    let stickers = state.dim().1 * state.dim().2;
+
+    for face in FACELET_ORDER {
+        let found = state.iter().filter(|f| **f == face).count();
+        if found != stickers {
+            return Err(StateError::StickerCount {
+                face: face.facelet(),
+                expected: stickers,
+                found,
+            });
+        }
+    }
+
+    check_state(state)
+}
+
 impl<T> Cube<T> {
-    fn get_dir(mut mv: String, dir: bool) -> Direction {
-        if dir {
-            mv.pop();
+    /// Greys out the stickers the mask hides, or shows them all again with `None`. A custom
+    /// mask must have the same shape as the state.
+    pub fn set_mask(&mut self, mask: Option<Mask>) -> Result<(), StateError> {
+        let visible = mask.map(|m| m.visible(self.size));
+        if let Some(visible) = &visible {
+            if visible.dim() != self.state.dim() {
+                return Err(StateError::MaskShape {
+                    expected: self.state.dim(),
+                    found: visible.dim(),
+                });
+            }
         }
-        let x = mv.parse::<i8>().unwrap_or(1) % 4;
-        Direction::from(if dir { (-x).rem_euclid(4) } else { x } as usize)
+
+        self.mask = visible;
+        Ok(())
     }
 
-    fn parse_moves(mv: &str) -> Move {
-        let a = mv.find(char::is_alphabetic).unwrap();
+    /// Starts tracking the original position and orientation of every sticker, counting from
+    /// the current state.
+    pub fn track_orientation(&mut self, marks: OrientationMarks) {
+        self.orientation = Some(Array3::from_shape_fn(self.state.dim(), |(f, row, col)| {
+            Sticker {
+                face: Face::from(f),
+                row,
+                col,
+                rotation: 0,
+            }
+        }));
+        self.orientation_marks = marks;
+    }
 
-        let pre: String = mv.chars().take(a).collect();
-        let suf: String = mv
-            .chars()
-            .rev()
-            .take_while(|x| !x.is_alphabetic())
-            .collect();
-        let mv: String = mv
-            .chars()
-            .skip(a)
-            .take_while(|x| x.is_alphabetic())
-            .collect();
+    pub fn orientation(&self) -> Option<&Array3<Sticker>> {
+        self.orientation.as_ref()
+    }
 
-        let wide = mv.contains('w');
-        let default = if wide { 2 } else { 1 };
-        let depth = pre.parse().unwrap_or(default) - 1;
-        let dir = Cube::<T>::get_dir(suf.clone(), suf.ends_with('\''));
+    /// Draws the letter of every sticker from `scheme`: the letter of the place the sticker
+    /// started from when tracking orientation, or else of the place it is on. Centers are only
+    /// lettered with `centers`, middle centers with the name of their face.
+    pub fn set_lettering(&mut self, scheme: Option<LetteringScheme>, centers: bool) {
+        self.lettering = scheme;
+        self.letter_centers = centers;
+    }
 
-        let f = mv.chars().next().unwrap();
+    fn sticker_letter(&self, face: Face, row: usize, col: usize) -> Option<String> {
+        let scheme = self.lettering.as_ref()?;
+        let size = self.size;
 
-        if wide && f.is_lowercase() {
-            panic!("Move cannot be wide and slice at the same time");
+        let position = match &self.orientation {
+            Some(orientation) => {
+                let sticker = orientation[[face as usize, row, col]];
+                (sticker.face, sticker.row, sticker.col)
+            }
+            None => (face, row, col),
+        };
+        let (face, row, col) = position;
+
+        let inner = (1..size - 1).contains(&row) && (1..size - 1).contains(&col);
+        if !inner {
+            scheme
+                .corner(position, size)
+                .or_else(|| scheme.edge(position, size))
+                .map(String::from)
+        } else if !self.letter_centers {
+            None
+        } else if size % 2 == 1 && row == size / 2 && col == size / 2 {
+            Some(face.to_string())
+        } else {
+            scheme.center(position, size).map(String::from)
         }
+    }
 
-        let face = Face::from(f);
-        Move {
-            face,
-            depth,
-            dir,
-            wide,
+    fn is_masked(&self, face: Face, row: usize, col: usize) -> bool {
+        match &self.mask {
+            Some(mask) => !mask[[face as usize, row, col]],
+            None => false,
         }
     }
 
-    fn draw_cube(&self, svg: &mut SVG) {
-        let gap = self.gap;
-        let size = self.size;
-        let cubie_size = self.cubie_size;
+    /// Sticker size and gap between faces, from the options or the cube's own.
+    fn layout(&self, options: &RenderOptions) -> (f64, f64) {
+        net::layout(options, self.cubie_size, self.gap)
+    }
+
+    fn title(&self) -> String {
+        format!("{0}x{0}x{0} cube", self.size)
+    }
+
+    /// The stickers of the first `rows` rows of each face, named by the face they belong on,
+    /// for screen readers.
+    fn describe(&self, faces: &[Face], rows: usize) -> String {
+        let faces: Vec<String> = faces
+            .iter()
+            .map(|&face| {
+                let rows: Vec<String> = (0..rows)
+                    .map(|row| {
+                        let stickers: Vec<String> = (0..self.size)
+                            .map(|col| {
+                                if self.is_masked(face, row, col) {
+                                    "hidden".to_string()
+                                } else {
+                                    self.state[[face as usize, row, col]].to_string()
+                                }
+                            })
+                            .collect();
+                        stickers.join(" ")
+                    })
+                    .collect();
+                format!("{} face: {}.", face, rows.join(", "))
+            })
+            .collect();
+        faces.join(" ")
+    }
+
+    /// Definitions shared by the stickers: the patterns of each face when drawing patterns
+    /// and the classes of each color when drawing compact stickers.
+    fn append_defs(&self, svg: &mut SVG, options: &RenderOptions) {
+        let (cubie_size, _) = self.layout(options);
+
+        if let Some(defs) = patterns(
+            "cube",
+            face_colors(&self.color_scheme, options),
+            cubie_size / 4.,
+            options,
+        ) {
+            svg.append(defs);
+        }
+        let masked = self.mask.as_ref().map(|_| Color::grey());
+        let colors = face_colors(&self.color_scheme, options).map(|(_, _, color)| color);
+        if let Some(style) = palette(colors.chain(masked), options) {
+            svg.append(style);
+        }
+    }
+
+    fn draw_cube(&self, svg: &mut SVG, options: &RenderOptions) {
+        let (cubie_size, gap) = self.layout(options);
+
+        self.append_defs(svg, options);
 
         let mut g = Group::new().set("transform", "translate(0.5,0.5)");
+        let painter = Painter {
+            puzzle: "cube",
+            sticker_size: cubie_size,
+            options,
+        };
+        for (x, y, face) in Net::cube(self.size).positions(cubie_size, gap) {
+            self.paint_cube_face(&mut g, &painter, (x, y), face);
+        }
 
-        self.paint_cube_face(
-            &mut g,
-            3 * gap + 2 * size * cubie_size,
-            2 * gap + size * cubie_size,
-            Face::R,
-        );
+        svg.append(g);
+    }
 
-        self.paint_cube_face(&mut g, 2 * gap + size * cubie_size, gap, Face::U);
+    fn paint_cube_face(&self, g: &mut Group, painter: &Painter, (x, y): (f64, f64), face: Face) {
+        let size = self.size;
+        let cubie_size = painter.sticker_size;
+
+        painter.face(
+            g,
+            (x, y),
+            (size, size),
+            |row, col| {
+                let color = self.sticker_color(face, row, col, painter.options);
+                let name = self.state[[face as usize, row, col]];
+                (color, (!self.is_masked(face, row, col)).then_some(name))
+            },
+            |g, row, col, (x, y), color| {
+                if let Some(mark) = self.orientation_mark(face, row, col, (x, y), cubie_size) {
+                    g.append(mark);
+                }
 
-        self.paint_cube_face(
-            &mut g,
-            2 * gap + size * cubie_size,
-            2 * gap + size * cubie_size,
-            Face::F,
+                if self.is_masked(face, row, col) {
+                    return;
+                }
+                if let Some(letter) = self.sticker_letter(face, row, col) {
+                    g.append(
+                        Text::new()
+                            .set("x", x + cubie_size / 2.)
+                            .set("y", y + cubie_size / 2.)
+                            .set("text-anchor", "middle")
+                            .set("dominant-baseline", "central")
+                            .set("font-family", "sans-serif")
+                            .set("font-size", cubie_size * 0.6)
+                            .set("fill", color.contrasting().to_string())
+                            .add(svg::node::Text::new(escape(&letter))),
+                    );
+                }
+            },
         );
+    }
 
-        self.paint_cube_face(&mut g, gap, 2 * gap + size * cubie_size, Face::L);
+    /// Notch pointing to where the top of the sticker is.
+    fn orientation_mark(
+        &self,
+        face: Face,
+        row: usize,
+        col: usize,
+        (x, y): (f64, f64),
+        c: f64,
+    ) -> Option<Path> {
+        let orientation = self.orientation.as_ref()?;
+
+        let inner = (1..self.size - 1).contains(&row) && (1..self.size - 1).contains(&col);
+        match self.orientation_marks {
+            OrientationMarks::Hidden => return None,
+            OrientationMarks::Centers if !inner => return None,
+            _ => {}
+        }
 
-        self.paint_cube_face(
-            &mut g,
-            2 * gap + size * cubie_size,
-            3 * gap + 2 * size * cubie_size,
-            Face::D,
-        );
+        let rotation = orientation[[face as usize, row, col]].rotation;
+        let (cx, cy) = (x + c / 2., y + c / 2.);
+
+        Some(
+            Path::new()
+                .set(
+                    "d",
+                    format!(
+                        "M{},{} L{},{} L{},{} z",
+                        cx,
+                        y + 0.15 * c,
+                        cx - 0.15 * c,
+                        y + 0.35 * c,
+                        cx + 0.15 * c,
+                        y + 0.35 * c
+                    ),
+                )
+                .set("fill", Color::black().to_string())
+                .set(
+                    "transform",
+                    format!("rotate({},{},{})", 90 * rotation, cx, cy),
+                ),
+        )
+    }
 
-        self.paint_cube_face(
-            &mut g,
-            4 * gap + 3 * size * cubie_size,
-            2 * gap + size * cubie_size,
-            Face::B,
-        );
+    fn sticker_color(&self, face: Face, row: usize, col: usize, options: &RenderOptions) -> Color {
+        let color_scheme = options.cube_colors.as_ref().unwrap_or(&self.color_scheme);
 
-        svg.append(g);
+        if self.is_masked(face, row, col) {
+            Color::grey()
+        } else if let Some(col) = color_scheme.get(&self.state[[face as usize, row, col]]) {
+            *col
+        } else {
+            Color::black()
+        }
     }
 
-    fn paint_cube_face(&self, g: &mut Group, x: usize, y: usize, face: Face) {
+    /// Draws the U face seen from above, with the top row of each side face as thin strips
+    /// around it, as on OLL and PLL algorithm sheets.
+    ///
+    /// With `arrows` set, the permutation of the last layer pieces is drawn as arrows going
+    /// from each piece to the position it belongs to.
+    pub fn draw_last_layer(&self, arrows: bool, options: &RenderOptions) -> SVG {
         let size = self.size;
-        let cubie_size = self.cubie_size;
+        let (cubie_size, gap) = self.layout(options);
+        let strip = cubie_size / 3.;
+
+        let inner = 2. * gap + strip;
+        let far = inner + size as f64 * cubie_size + gap;
+        let length = 2. * inner + size as f64 * cubie_size;
+
+        let mut svg = document(
+            length,
+            length,
+            options,
+            &format!("{} last layer", self.title()),
+            || {
+                format!(
+                    "{} {}",
+                    self.describe(&[Face::U], size),
+                    self.describe(&[Face::F, Face::R, Face::B, Face::L], 1)
+                )
+            },
+        );
+        self.append_defs(&mut svg, options);
 
+        let mut g = Group::new().set("transform", "translate(0.5,0.5)");
+
+        let side = size as f64 * cubie_size;
+        if let Some(body) = body(inner, inner, side, side, options) {
+            g.append(body);
+        }
+
+        let mut stickers = Stickers::new(&g, options);
         for row in 0..size {
             for col in 0..size {
-                let x = x + col * cubie_size;
-                let y = y + row * cubie_size;
-
-                let color = if let Some(col) = self
-                    .color_scheme
-                    .get(&self.state.slice(s![face as usize, .., ..])[[row, col]])
-                {
-                    *col
-                } else {
-                    Color::black()
-                };
+                stickers.rect(
+                    &mut g,
+                    (
+                        inner + col as f64 * cubie_size,
+                        inner + row as f64 * cubie_size,
+                    ),
+                    (cubie_size, cubie_size),
+                    self.sticker_color(Face::U, row, col, options),
+                );
+
+                if !self.is_masked(Face::U, row, col) {
+                    mark_face(
+                        &mut g,
+                        "cube",
+                        self.state[[Face::U as usize, row, col]],
+                        (
+                            inner + col as f64 * cubie_size,
+                            inner + row as f64 * cubie_size,
+                        ),
+                        cubie_size,
+                        self.sticker_color(Face::U, row, col, options),
+                        options,
+                    );
+                }
+            }
+        }
+
+        for i in 0..size {
+            let offset = inner + i as f64 * cubie_size;
+
+            stickers.rect(
+                &mut g,
+                (offset, gap),
+                (cubie_size, strip),
+                self.sticker_color(Face::B, 0, size - i - 1, options),
+            );
+            stickers.rect(
+                &mut g,
+                (offset, far),
+                (cubie_size, strip),
+                self.sticker_color(Face::F, 0, i, options),
+            );
+            stickers.rect(
+                &mut g,
+                (gap, offset),
+                (strip, cubie_size),
+                self.sticker_color(Face::L, 0, i, options),
+            );
+            stickers.rect(
+                &mut g,
+                (far, offset),
+                (strip, cubie_size),
+                self.sticker_color(Face::R, 0, size - i - 1, options),
+            );
+        }
 
-                let rect = Rectangle::new()
-                    .set("x", x)
-                    .set("y", y)
-                    .set("width", cubie_size)
-                    .set("height", cubie_size)
+        stickers.finish(&mut g);
+
+        if arrows {
+            let marker = Marker::new()
+                .set("id", "arrowhead")
+                .set("viewBox", (0, 0, 10, 10))
+                .set("refX", 8)
+                .set("refY", 5)
+                .set("markerWidth", 4)
+                .set("markerHeight", 4)
+                .set("orient", "auto")
+                .add(Path::new().set("d", "M0,0 L10,5 L0,10 z"));
+            svg.append(Definitions::new().add(marker));
+
+            let center = |(row, col): (usize, usize)| {
+                (
+                    inner + (col as f64 + 0.5) * cubie_size,
+                    inner + (row as f64 + 0.5) * cubie_size,
+                )
+            };
+
+            for (from, to) in self.last_layer_permutation() {
+                let (x1, y1) = center(from);
+                let (x2, y2) = center(to);
+
+                let shorten = 0.2 * cubie_size / (x2 - x1).hypot(y2 - y1);
+                let (dx, dy) = ((x2 - x1) * shorten, (y2 - y1) * shorten);
+
+                g.append(
+                    Line::new()
+                        .set("x1", x1 + dx)
+                        .set("y1", y1 + dy)
+                        .set("x2", x2 - dx)
+                        .set("y2", y2 - dy)
+                        .set("stroke", Color::black().to_string())
+                        .set("stroke-width", cubie_size / 10.)
+                        .set("marker-end", "url(#arrowhead)"),
+                );
+            }
+        }
+
+        svg.append(g);
+        svg
+    }
+
+    /// The net with lines of text under it, such as a blindfolded memo.
+    pub fn draw_with_caption(&self, caption: &str, options: &RenderOptions) -> SVG {
+        let (width, height) = self.get_preferred_size(options);
+        let (line_height, gap) = self.layout(options);
+        let lines: Vec<&str> = caption.lines().collect();
+
+        let mut svg = document(
+            width,
+            height + lines.len() as f64 * line_height + gap,
+            options,
+            &self.title(),
+            || format!("{} {}", self.describe(&FACELET_ORDER, self.size), caption),
+        );
+        self.draw_cube(&mut svg, options);
+
+        let color = match options.background {
+            Some(background) => background.contrasting(),
+            None => Color::black(),
+        };
+        for (i, line) in lines.into_iter().enumerate() {
+            svg.append(
+                Text::new()
+                    .set("x", gap)
+                    .set("y", height + (i + 1) as f64 * line_height)
+                    .set("font-family", "monospace")
+                    .set("font-size", line_height * 0.8)
                     .set("fill", color.to_string())
-                    .set("stroke", Color::black().to_string());
+                    .add(svg::node::Text::new(escape(line))),
+            );
+        }
+        svg
+    }
+
+    /// Faces of the stickers making up the last layer piece at `(row, col)` on the U face, in
+    /// the given state.
+    fn last_layer_piece(&self, state: &Array3<Face>, row: usize, col: usize) -> Vec<Face> {
+        let last = self.size - 1;
+
+        let mut piece = vec![state[[Face::U as usize, row, col]]];
+        if row == 0 {
+            piece.push(state[[Face::B as usize, 0, last - col]]);
+        }
+        if row == last {
+            piece.push(state[[Face::F as usize, 0, col]]);
+        }
+        if col == 0 {
+            piece.push(state[[Face::L as usize, 0, row]]);
+        }
+        if col == last {
+            piece.push(state[[Face::R as usize, 0, last - row]]);
+        }
+
+        piece.sort_by_key(|f| *f as usize);
+        piece
+    }
+
+    /// Pairs of U face positions `(from, to)` for every last layer piece that is not in its
+    /// solved position. Pieces sharing the same colors go to the first free position.
+    fn last_layer_permutation(&self) -> Vec<((usize, usize), (usize, usize))> {
+        let last = self.size - 1;
+        let solved = Array3::<Face>::from_shape_fn(self.state.dim(), |(i, _, _)| Face::from(i));
+
+        let positions: Vec<(usize, usize)> = (0..self.size)
+            .flat_map(|row| (0..self.size).map(move |col| (row, col)))
+            .filter(|(row, col)| *row == 0 || *row == last || *col == 0 || *col == last)
+            .collect();
+
+        let mut taken = vec![false; positions.len()];
+        let mut targets = vec![None; positions.len()];
+
+        // Pieces already solved keep their position, the others take the first free one.
+        for pass in 0..2 {
+            for (i, &(row, col)) in positions.iter().enumerate() {
+                if targets[i].is_some() {
+                    continue;
+                }
+                let piece = self.last_layer_piece(&self.state, row, col);
+
+                let target = positions.iter().enumerate().position(|(j, &(r, c))| {
+                    (pass == 1 || i == j)
+                        && !taken[j]
+                        && self.last_layer_piece(&solved, r, c) == piece
+                });
 
-                g.append(rect);
+                if let Some(j) = target {
+                    taken[j] = true;
+                    targets[i] = Some(j);
+                }
             }
         }
+
+        positions
+            .iter()
+            .zip(targets)
+            .filter_map(|(from, to)| to.map(|j| (*from, positions[j])))
+            .filter(|(from, to)| from != to)
+            .collect()
+    }
+
+    fn get_preferred_size(&self, options: &RenderOptions) -> (f64, f64) {
+        let (cubie_size, gap) = self.layout(options);
+        Net::cube(self.size).size(cubie_size, gap)
     }
 
-    fn get_preferred_size(&self) -> (usize, usize) {
-        let width = (self.size * self.cubie_size + self.gap) * 4 + self.gap;
-        let height = (self.size * self.cubie_size + self.gap) * 3 + self.gap;
+    fn apply_move(&mut self, mv: &LayerTurn) {
+        let Some(table) = move_table(Net::cube(self.size), mv) else {
+            return;
+        };
 
-        (width, height)
+        table.apply(stickers(&mut self.state));
+        if let Some(orientation) = &mut self.orientation {
+            table.apply(stickers(orientation));
+        }
+    }
+}
+
+/// The stickers of a state in the order of the move tables.
+fn stickers<E: Clone>(state: &mut Array3<E>) -> &mut [E] {
+    if !state.is_standard_layout() {
+        *state = state.as_standard_layout().into_owned();
     }
+    state.as_slice_mut().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::Face::{B, D, F, L, R, U};
+    use crate::puzzles::fixtures;
+    use crate::puzzles::net::Turnable;
+    use crate::utils::maths::rotate_2d_matrix;
+    use ndarray::arr3;
+    use ndarray::Array1;
+    use oorandom::Rand32;
 
-    fn get_layer_idx(&self, i: usize, cond: bool) -> [usize; 2] {
+    // The layer by layer turns the move tables were first built from, to check the tables
+    // against.
+    fn get_layer_idx(size: usize, i: usize, cond: bool) -> [usize; 2] {
         if cond {
-            [self.size - i - 1, i]
+            [size - i - 1, i]
         } else {
-            [i, self.size - i - 1]
+            [i, size - i - 1]
         }
     }
 
-    fn ap(&mut self, mv: &Move) {
+    fn turn<E: Turnable>(state: &mut Array3<E>, mv: &LayerTurn) {
+        let size = state.dim().1;
         let clockwise = mv.dir == Direction::Clockwise;
-        let mut face = self.state.slice_mut(s![mv.face as usize, .., ..]);
-        face.assign(&rotate_2d_matrix(&mut face.to_owned(), clockwise));
+
+        // The face turns with its layer, and the opposite face turns the other way when the
+        // last layer is part of the move.
+        if mv.depth == 0 || mv.wide {
+            rotate_face(state, mv.face, clockwise);
+        }
+        if mv.depth == size - 1 {
+            rotate_face(state, mv.face.opposite(), !clockwise);
+        }
 
         match mv {
-            Move {
+            LayerTurn {
                 face: Face::R | Face::L,
                 wide,
                 depth,
@@ -355,14 +1393,14 @@ impl<T> Cube<T> {
                     _ => panic!(),
                 };
 
-                self.rotate_rl_layer(r, self.get_layer_idx(*depth, mv.face == Face::R));
+                rotate_rl_layer(state, r, get_layer_idx(size, *depth, mv.face == Face::R));
                 if *wide {
                     for i in 0..*depth {
-                        self.rotate_rl_layer(r, self.get_layer_idx(i, mv.face == Face::R));
+                        rotate_rl_layer(state, r, get_layer_idx(size, i, mv.face == Face::R));
                     }
                 }
             }
-            Move {
+            LayerTurn {
                 face: Face::U | Face::D,
                 wide,
                 depth,
@@ -379,14 +1417,14 @@ impl<T> Cube<T> {
                     _ => panic!(),
                 };
 
-                self.rotate_ud_layer(r, self.get_layer_idx(*depth, mv.face == Face::D)[0]);
+                rotate_ud_layer(state, r, get_layer_idx(size, *depth, mv.face == Face::D)[0]);
                 if *wide {
                     for i in 0..*depth {
-                        self.rotate_ud_layer(r, self.get_layer_idx(i, mv.face == Face::D)[0]);
+                        rotate_ud_layer(state, r, get_layer_idx(size, i, mv.face == Face::D)[0]);
                     }
                 }
             }
-            Move {
+            LayerTurn {
                 face: Face::F | Face::B,
                 wide,
                 depth,
@@ -398,161 +1436,136 @@ impl<T> Cube<T> {
                     [Face::D, Face::R, Face::U, Face::L]
                 };
 
-                let mut rotate_fb = if mv.face == Face::F && !clockwise
-                    || mv.face == Face::B && clockwise
-                {
-                    Box::new(|a, b| self.rev_rotate_fb_layer(a, b))
-                        as Box<dyn FnMut([Face; 4], usize)>
-                } else {
-                    Box::new(|a, b| self.rotate_fb_layer(a, b)) as Box<dyn FnMut([Face; 4], usize)>
-                };
+                let rotate_fb =
+                    if mv.face == Face::F && !clockwise || mv.face == Face::B && clockwise {
+                        rev_rotate_fb_layer
+                    } else {
+                        rotate_fb_layer
+                    };
 
-                rotate_fb(r, *depth);
+                rotate_fb(state, r, *depth);
                 if *wide {
                     for i in 0..*depth {
-                        rotate_fb(r, i);
+                        rotate_fb(state, r, i);
                     }
                 }
             }
         }
     }
 
-    fn apply_move(&mut self, mv: &mut Move) {
-        match mv.dir {
-            Direction::None => {}
-            Direction::Half => {
-                mv.dir = Direction::Clockwise;
-                self.ap(mv);
-                self.ap(mv);
-            }
-            Direction::Clockwise | Direction::Counterclockwise => {
-                self.ap(mv);
-            }
-        }
+    fn rotate_face<E: Turnable>(state: &mut Array3<E>, face: Face, clockwise: bool) {
+        let mut face = state.slice_mut(s![face as usize, .., ..]);
+        let rotated = rotate_2d_matrix(&mut face.to_owned(), clockwise);
+        face.assign(&rotated.mapv(|e| e.turned(if clockwise { 1 } else { 3 })));
     }
 
-    fn rotate_rl_layer(&mut self, r: [Face; 4], side: [usize; 2]) {
-        let tmp: Array1<Face> = self
-            .state
+    /// Stickers moved between U, F and D keep their orientation in the net, while those going
+    /// through B are turned upside down.
+    fn rotate_rl_layer<E: Turnable>(state: &mut Array3<E>, r: [Face; 4], side: [usize; 2]) {
+        let tmp: Array1<E> = state
             .slice(s![r[0] as usize, .., side[0]])
             .iter()
-            .copied()
+            .map(|e| e.turned(2))
             .rev()
             .collect();
 
-        let b = self.state.slice(s![r[1] as usize, .., side[0]]).to_owned();
-        self.state
-            .slice_mut(s![r[0] as usize, .., side[0]])
-            .assign(&b);
+        let b = state.slice(s![r[1] as usize, .., side[0]]).to_owned();
+        state.slice_mut(s![r[0] as usize, .., side[0]]).assign(&b);
 
-        let c = self.state.slice(s![r[2] as usize, .., side[0]]).to_owned();
-        self.state
-            .slice_mut(s![r[1] as usize, .., side[0]])
-            .assign(&c);
+        let c = state.slice(s![r[2] as usize, .., side[0]]).to_owned();
+        state.slice_mut(s![r[1] as usize, .., side[0]]).assign(&c);
 
-        let d: Array1<Face> = self
-            .state
+        let d: Array1<E> = state
             .slice(s![r[3] as usize, .., side[1]])
             .iter()
-            .copied()
+            .map(|e| e.turned(2))
             .rev()
             .collect();
-        self.state
-            .slice_mut(s![r[2] as usize, .., side[0]])
-            .assign(&d);
-        self.state
-            .slice_mut(s![r[3] as usize, .., side[1]])
-            .assign(&tmp);
+        state.slice_mut(s![r[2] as usize, .., side[0]]).assign(&d);
+        state.slice_mut(s![r[3] as usize, .., side[1]]).assign(&tmp);
     }
 
-    fn rotate_ud_layer(&mut self, r: [Face; 4], side: usize) {
-        let tmp = self.state.slice(s![r[0] as usize, side, ..]).to_owned();
+    fn rotate_ud_layer<E: Turnable>(state: &mut Array3<E>, r: [Face; 4], side: usize) {
+        let tmp = state.slice(s![r[0] as usize, side, ..]).to_owned();
         for i in 0..3 {
-            let b = self.state.slice(s![r[i + 1] as usize, side, ..]).to_owned();
-            self.state.slice_mut(s![r[i] as usize, side, ..]).assign(&b);
+            let b = state.slice(s![r[i + 1] as usize, side, ..]).to_owned();
+            state.slice_mut(s![r[i] as usize, side, ..]).assign(&b);
         }
-        self.state
-            .slice_mut(s![r[3] as usize, side, ..])
-            .assign(&tmp);
+        state.slice_mut(s![r[3] as usize, side, ..]).assign(&tmp);
     }
 
-    fn rotate_fb_layer(&mut self, r: [Face; 4], layer: usize) {
-        let tmp = self
-            .state
-            .slice(s![r[0] as usize, self.size - layer - 1, ..])
-            .to_owned();
+    /// Stickers going around the F and B axis are turned a quarter clockwise in the net.
+    fn rotate_fb_layer<E: Turnable>(state: &mut Array3<E>, r: [Face; 4], layer: usize) {
+        let size = state.dim().1;
 
-        let b: Array1<Face> = self
-            .state
-            .slice(s![r[1] as usize, .., self.size - layer - 1])
+        let tmp = state
+            .slice(s![r[0] as usize, size - layer - 1, ..])
+            .mapv(|e| e.turned(1));
+
+        let b: Array1<E> = state
+            .slice(s![r[1] as usize, .., size - layer - 1])
             .iter()
-            .copied()
+            .map(|e| e.turned(1))
             .rev()
             .collect();
-        self.state
-            .slice_mut(s![r[0] as usize, self.size - layer - 1, ..])
+        state
+            .slice_mut(s![r[0] as usize, size - layer - 1, ..])
             .assign(&b);
 
-        let c = self.state.slice(s![r[2] as usize, layer, ..]).to_owned();
-        self.state
-            .slice_mut(s![r[1] as usize, .., self.size - layer - 1])
+        let c = state
+            .slice(s![r[2] as usize, layer, ..])
+            .mapv(|e| e.turned(1));
+        state
+            .slice_mut(s![r[1] as usize, .., size - layer - 1])
             .assign(&c);
 
-        let d: Array1<Face> = self
-            .state
+        let d: Array1<E> = state
             .slice(s![r[3] as usize, .., layer])
             .iter()
-            .copied()
+            .map(|e| e.turned(1))
             .rev()
             .collect();
-        self.state
-            .slice_mut(s![r[2] as usize, layer, ..])
-            .assign(&d);
+        state.slice_mut(s![r[2] as usize, layer, ..]).assign(&d);
 
-        self.state
-            .slice_mut(s![r[3] as usize, .., layer])
-            .assign(&tmp);
+        state.slice_mut(s![r[3] as usize, .., layer]).assign(&tmp);
     }
 
-    fn rev_rotate_fb_layer(&mut self, r: [Face; 4], layer: usize) {
-        let tmp = self
-            .state
-            .slice(s![r[2] as usize, self.size - layer - 1, ..])
-            .to_owned();
+    /// Stickers going around the F and B axis are turned a quarter counterclockwise in the net.
+    fn rev_rotate_fb_layer<E: Turnable>(state: &mut Array3<E>, r: [Face; 4], layer: usize) {
+        let size = state.dim().1;
+
+        let tmp = state
+            .slice(s![r[2] as usize, size - layer - 1, ..])
+            .mapv(|e| e.turned(3));
 
-        let b = self.state.slice(s![r[1] as usize, .., layer]).to_owned();
-        self.state
-            .slice_mut(s![r[2] as usize, self.size - layer - 1, ..])
+        let b = state
+            .slice(s![r[1] as usize, .., layer])
+            .mapv(|e| e.turned(3));
+        state
+            .slice_mut(s![r[2] as usize, size - layer - 1, ..])
             .assign(&b);
 
-        for i in 0..self.size {
-            self.state[[r[1] as usize, i, layer]] =
-                self.state[[r[0] as usize, layer, self.size - i - 1]];
+        for i in 0..size {
+            state[[r[1] as usize, i, layer]] =
+                state[[r[0] as usize, layer, size - i - 1]].turned(3);
         }
 
-        for j in 0..self.size {
-            self.state[[r[0] as usize, layer, j]] =
-                self.state[[r[3] as usize, j, self.size - layer - 1]];
+        for j in 0..size {
+            state[[r[0] as usize, layer, j]] =
+                state[[r[3] as usize, j, size - layer - 1]].turned(3);
         }
 
-        for i in 0..self.size {
-            self.state[[r[3] as usize, i, self.size - layer - 1]] = tmp[self.size - i - 1];
+        for i in 0..size {
+            state[[r[3] as usize, i, size - layer - 1]] = tmp[size - i - 1];
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::puzzles::cube::Face::{B, D, F, L, R, U};
-    use ndarray::arr3;
 
     #[test]
     fn three_by_three() {
         {
             let mut cube = Cube::<ThreeByThree>::new();
 
-            cube.apply_scramble("U F' U2 F R2 B' U2 L2 R2 F D2 R2 U' B2 U' R B' F' L D2 U");
+            cube.apply_scramble(fixtures::THREE_BY_THREE[0]);
 
             assert_eq!(
                 cube.state,
@@ -569,7 +1582,7 @@ mod tests {
         {
             let mut cube = Cube::<ThreeByThree>::new();
 
-            cube.apply_scramble("B F2 U2 B2 F2 R U2 L2 R U' B D2 L' D' L2 U2 B D' F2");
+            cube.apply_scramble(fixtures::THREE_BY_THREE[1]);
 
             assert_eq!(
                 cube.state,
@@ -586,7 +1599,7 @@ mod tests {
         {
             let mut cube = Cube::<ThreeByThree>::new();
 
-            cube.apply_scramble("F U R U2 B U2 B' D2 B L2 D2 L2 D F U L' D' L B2");
+            cube.apply_scramble(fixtures::THREE_BY_THREE[2]);
 
             assert_eq!(
                 cube.state,
@@ -603,7 +1616,7 @@ mod tests {
         {
             let mut cube = Cube::<ThreeByThree>::new();
 
-            cube.apply_scramble("U2 R F2 L D2 B2 F2 L2 U' L' U2 L2 D F U B' L2 U F2");
+            cube.apply_scramble(fixtures::THREE_BY_THREE[3]);
 
             assert_eq!(
                 cube.state,
@@ -620,7 +1633,7 @@ mod tests {
         {
             let mut cube = Cube::<ThreeByThree>::new();
 
-            cube.apply_scramble("U' L2 U L' U2 D' F' B' R2 F' R B L2 U2 B D2 F2");
+            cube.apply_scramble(fixtures::THREE_BY_THREE[4]);
 
             assert_eq!(
                 cube.state,
@@ -636,12 +1649,687 @@ mod tests {
         }
     }
 
+    #[test]
+    fn masks() {
+        let count = |mask: Mask, size| mask.visible(size).iter().filter(|x| **x).count();
+
+        assert_eq!(count(Mask::Cross, 3), 13);
+        assert_eq!(count(Mask::F2l, 3), 33);
+        assert_eq!(count(Mask::Oll, 3), 9);
+        assert_eq!(count(Mask::Pll, 3), 21);
+        assert_eq!(count(Mask::Coll, 3), 17);
+        assert_eq!(count(Mask::Zbll, 3), 21);
+        assert_eq!(count(Mask::FirstLayer, 2), 12);
+        assert_eq!(count(Mask::Cll, 2), 12);
+        assert_eq!(count(Mask::Oll, 2), 4);
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.set_mask(Some(Mask::Oll)).unwrap();
+
+        let svg = cube.draw().to_string();
+        assert_eq!(svg.matches(&Color::grey().to_string()).count(), 45);
+
+        cube.set_mask(None).unwrap();
+        let svg = cube.draw().to_string();
+        assert_eq!(svg.matches(&Color::grey().to_string()).count(), 0);
+
+        assert_eq!(
+            cube.set_mask(Some(Mask::Custom(Array3::from_elem((6, 2, 2), true)))),
+            Err(StateError::MaskShape {
+                expected: (6, 3, 3),
+                found: (6, 2, 2)
+            })
+        );
+        assert_eq!(cube.mask, None);
+    }
+
+    #[test]
+    fn last_layer() {
+        let mut cube = Cube::<ThreeByThree>::new();
+
+        let svg = cube
+            .draw_last_layer(true, &RenderOptions::default())
+            .to_string();
+        assert_eq!(svg.matches("<rect").count(), 21);
+        assert_eq!(svg.matches("<line").count(), 0);
+
+        cube.apply_scramble("R U R' U' R' F R2 U' R' U' R U R' F'");
+
+        assert_eq!(
+            cube.last_layer_permutation(),
+            vec![
+                ((0, 2), (2, 2)),
+                ((1, 0), (1, 2)),
+                ((1, 2), (1, 0)),
+                ((2, 2), (0, 2))
+            ]
+        );
+
+        let svg = cube
+            .draw_last_layer(true, &RenderOptions::default())
+            .to_string();
+        assert_eq!(svg.matches("<line").count(), 4);
+
+        let svg = cube
+            .draw_last_layer(false, &RenderOptions::default())
+            .to_string();
+        assert_eq!(svg.matches("<line").count(), 0);
+
+        let mut cube = Cube::<FourByFour>::new();
+        cube.apply_scramble("U");
+
+        assert_eq!(
+            cube.draw_last_layer(false, &RenderOptions::default())
+                .to_string()
+                .matches("<rect")
+                .count(),
+            32
+        );
+        assert_eq!(cube.last_layer_permutation().len(), 12);
+    }
+
+    #[test]
+    fn from_facelets() {
+        let solved = "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
+        assert_eq!(
+            Cube::<ThreeByThree>::from_facelets(solved).unwrap().state,
+            Cube::<ThreeByThree>::new().state
+        );
+
+        for scramble in [
+            "U F' U2 F R2 B' U2 L2 R2 F D2 R2 U' B2 U' R B' F' L D2 U",
+            "R Rw' U Rw R' F2",
+            "Uw U' R Fw' F",
+        ] {
+            let mut cube = Cube::<ThreeByThree>::new();
+            cube.apply_scramble(scramble);
+
+            let imported = Cube::<ThreeByThree>::from_facelets(&cube.to_facelets()).unwrap();
+            assert_eq!(imported.state, cube.state);
+        }
+
+        let mut cube = Cube::<FourByFour>::new();
+        cube.apply_scramble("Rw U2 Fw' D L2 Bw");
+        let imported = Cube::<FourByFour>::from_facelets(&cube.to_facelets()).unwrap();
+        assert_eq!(imported.state, cube.state);
+
+        let kociemba = "DRLUUBFBRBLURRLRUBLRDDFDLFUFUFFDBRDUBRUFLLFDDBFLUBLRBD";
+        assert!(Cube::<ThreeByThree>::from_facelets(kociemba).is_ok());
+
+        let with = |i: usize, c: &str| {
+            let mut s = solved.to_string();
+            s.replace_range(i..=i, c);
+            Cube::<ThreeByThree>::from_facelets(&s).map(|_| ())
+        };
+        let swapped = |a: usize, b: usize| {
+            let mut s: Vec<char> = solved.chars().collect();
+            s.swap(a, b);
+            Cube::<ThreeByThree>::from_facelets(&s.into_iter().collect::<String>()).map(|_| ())
+        };
+
+        assert_eq!(
+            Cube::<ThreeByThree>::from_facelets("UUU").map(|_| ()),
+            Err(StateError::Length {
+                expected: 54,
+                found: 3
+            })
+        );
+        assert_eq!(
+            with(3, "X"),
+            Err(StateError::Facelet {
+                index: 3,
+                found: 'X'
+            })
+        );
+        assert_eq!(
+            with(0, "R"),
+            Err(StateError::StickerCount {
+                face: 'U',
+                expected: 9,
+                found: 8
+            })
+        );
+        // URF corner twisted in place
+        assert_eq!(
+            {
+                let mut s: Vec<char> = solved.chars().collect();
+                (s[8], s[9], s[20]) = ('F', 'U', 'R');
+                Cube::<ThreeByThree>::from_facelets(&s.into_iter().collect::<String>()).map(|_| ())
+            },
+            Err(StateError::CornerTwist)
+        );
+        // UR edge flipped in place
+        assert_eq!(swapped(5, 10), Err(StateError::EdgeFlip));
+        // UR and UB edges swapped
+        assert_eq!(
+            {
+                let mut s: Vec<char> = solved.chars().collect();
+                (s[1], s[46], s[5], s[10]) = ('U', 'R', 'U', 'B');
+                Cube::<ThreeByThree>::from_facelets(&s.into_iter().collect::<String>()).map(|_| ())
+            },
+            Err(StateError::Parity)
+        );
+        // U and F centers swapped
+        assert_eq!(swapped(4, 22), Err(StateError::Centers));
+        // Two stickers of the URF corner swapped, mirroring it
+        assert_eq!(swapped(8, 9), Err(StateError::Corner { position: "URF" }));
+    }
+
+    #[test]
+    fn export() {
+        let mut cube = Cube::<TwoByTwo>::new();
+        assert_eq!(cube.to_facelets(), "UUUURRRRFFFFDDDDLLLLBBBB");
+
+        cube.apply_scramble("R");
+        assert_eq!(cube.to_facelets(), "UFUFRRRRFDFDDBDBLLLLUBUB");
+        assert_eq!(
+            cube.to_json(),
+            "{\"puzzle\":\"cube\",\"size\":2,\"faces\":{\
+             \"U\":[\"U\",\"F\",\"U\",\"F\"],\
+             \"R\":[\"R\",\"R\",\"R\",\"R\"],\
+             \"F\":[\"F\",\"D\",\"F\",\"D\"],\
+             \"D\":[\"D\",\"B\",\"D\",\"B\"],\
+             \"L\":[\"L\",\"L\",\"L\",\"L\"],\
+             \"B\":[\"U\",\"B\",\"U\",\"B\"]}}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut cube = Cube::<FourByFour>::new();
+        cube.apply_scramble("Rw U2 Fw' D L2 Bw");
+        cube.set_mask(Some(Mask::F2l)).unwrap();
+
+        let json = serde_json::to_string(&cube).unwrap();
+        let de: Cube<FourByFour> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(de.state, cube.state);
+        assert_eq!(de.mask, cube.mask);
+        assert_eq!(de.draw().to_string(), cube.draw().to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_malformed() {
+        use serde_json::{json, Value};
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.track_orientation(OrientationMarks::All);
+        let json: Value = serde_json::to_value(&cube).unwrap();
+        let error = |change: &dyn Fn(&mut Value)| {
+            let mut json = json.clone();
+            change(&mut json);
+            serde_json::from_value::<Cube<ThreeByThree>>(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(serde_json::from_value::<Cube<ThreeByThree>>(json.clone()).is_ok());
+        assert_eq!(
+            error(&|j| j["size"] = json!(4)),
+            "expected 3 layers, found 4"
+        );
+        assert_eq!(
+            error(&|j| {
+                j["state"]["dim"] = json!([2, 3, 3]);
+                j["state"]["data"] = json!(vec!["U"; 18]);
+            }),
+            "state has shape (2, 3, 3), expected (6, 3, 3)"
+        );
+        assert_eq!(
+            error(&|j| j["state"]["data"][0] = json!("U")),
+            "face U should have 9 stickers, found 10"
+        );
+        assert_eq!(
+            error(&|j| j["state"]["data"].as_array_mut().unwrap().swap(1, 10)),
+            "stickers at UR do not form an edge"
+        );
+        assert_eq!(
+            error(&|j| j["mask"] = json!({"v": 1, "dim": [6, 2, 2], "data": vec![true; 24]})),
+            "mask has shape (6, 2, 2), expected (6, 3, 3)"
+        );
+        assert_eq!(
+            error(&|j| j["orientation"]["data"][4]["row"] = json!(3)),
+            "tracked orientation does not match the puzzle"
+        );
+        assert!(serde_json::from_str::<Cube<ThreeByThree>>("{\"size\":3}").is_err());
+    }
+
+    fn assert_home<T>(cube: &Cube<T>, twisted: &[(Face, u8)]) {
+        for ((f, row, col), sticker) in cube.orientation().unwrap().indexed_iter() {
+            assert_eq!(
+                (sticker.face, sticker.row, sticker.col),
+                (Face::from(f), row, col)
+            );
+
+            let center = row == cube.size / 2 && col == cube.size / 2;
+            let rotation = twisted
+                .iter()
+                .find(|(face, _)| center && *face == Face::from(f))
+                .map_or(0, |(_, rotation)| *rotation);
+            assert_eq!(sticker.rotation, rotation, "{f} {row} {col}");
+        }
+    }
+
+    #[test]
+    fn lettering() {
+        let mut cube = Cube::<ThreeByThree>::new();
+        assert_eq!(cube.draw().to_string().matches("<text").count(), 0);
+
+        cube.set_lettering(Some(LetteringScheme::speffz()), false);
+        let svg = cube.draw().to_string();
+        assert_eq!(svg.matches("<text").count(), 48);
+        assert_eq!(svg.matches("fill=\"#FFFFFF\" font-family").count(), 16);
+
+        cube.set_lettering(Some(LetteringScheme::speffz()), true);
+        assert_eq!(cube.draw().to_string().matches("<text").count(), 54);
+        assert_eq!(cube.sticker_letter(U, 1, 1), Some("U".to_string()));
+
+        cube.apply_scramble("U");
+        assert_eq!(cube.sticker_letter(U, 0, 0), Some("A".to_string()));
+        cube.track_orientation(OrientationMarks::Hidden);
+        cube.apply_scramble("U");
+        assert_eq!(cube.sticker_letter(U, 0, 0), Some("D".to_string()));
+        assert_eq!(cube.sticker_letter(F, 0, 0), Some("M".to_string()));
+
+        let mut cube = Cube::<FourByFour>::new();
+        cube.set_lettering(Some(LetteringScheme::speffz()), false);
+        assert_eq!(cube.draw().to_string().matches("<text").count(), 48);
+        cube.set_lettering(Some(LetteringScheme::speffz()), true);
+        assert_eq!(cube.draw().to_string().matches("<text").count(), 72);
+
+        let letters = "<&CDEFGHIJKLMNOPQRSTUVWX";
+        let scheme = LetteringScheme::new(letters, letters).unwrap();
+        cube.set_lettering(Some(scheme), false);
+        let svg = cube.draw().to_string();
+        assert!(svg.contains("\n&lt;\n") && svg.contains("\n&amp;\n"));
+        assert_eq!(svg.matches('<').count(), svg.matches('>').count());
+    }
+
+    #[test]
+    fn orientation() {
+        for (sequence, twisted) in [
+            ("R U", [(R, 1), (U, 1)]),
+            ("F D", [(F, 1), (D, 1)]),
+            ("L' B'", [(L, 3), (B, 3)]),
+        ] {
+            let mut cube = Cube::<ThreeByThree>::new();
+            cube.track_orientation(OrientationMarks::Centers);
+
+            cube.apply_scramble(&[sequence; 105].join(" "));
+
+            assert_eq!(cube.state, Cube::<ThreeByThree>::new().state);
+            assert_home(&cube, &twisted);
+        }
+
+        let mut cube = Cube::<FiveByFive>::new();
+        cube.track_orientation(OrientationMarks::All);
+        cube.apply_scramble("Rw U Fw' D2 Bw L' 3Uw B2 R' Dw");
+        assert!(cube.orientation().unwrap().iter().any(|s| s.rotation != 0));
+        cube.apply_scramble("Dw' R B2 3Uw' L Bw' D2 Fw U' Rw'");
+        assert_home(&cube, &[]);
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.track_orientation(OrientationMarks::Centers);
+        assert_eq!(cube.draw().to_string().matches("<path").count(), 6);
+
+        cube.track_orientation(OrientationMarks::All);
+        cube.apply_scramble("R");
+        let svg = cube.draw().to_string();
+        assert_eq!(svg.matches("<path").count(), 54);
+        assert_eq!(svg.matches("rotate(90,").count(), 9);
+        assert_eq!(svg.matches("rotate(180,").count(), 6);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let parsed = |mv: &str| parse_move(mv).map(|mv| (mv.face, mv.dir, mv.layers, mv.wide));
+        assert_eq!(
+            parsed("R"),
+            Some((Face::R, Direction::Clockwise, Layers::Depth(0), false))
+        );
+        assert_eq!(
+            parsed("R2'"),
+            Some((Face::R, Direction::Half, Layers::Depth(0), false))
+        );
+        assert_eq!(
+            parsed("U3"),
+            Some((
+                Face::U,
+                Direction::Counterclockwise,
+                Layers::Depth(0),
+                false
+            ))
+        );
+        assert_eq!(
+            parsed("F4"),
+            Some((Face::F, Direction::None, Layers::Depth(0), false))
+        );
+        assert_eq!(
+            parsed("3Rw'"),
+            Some((Face::R, Direction::Counterclockwise, Layers::Depth(2), true))
+        );
+        assert_eq!(
+            parsed("Bw"),
+            Some((Face::B, Direction::Clockwise, Layers::Depth(1), true))
+        );
+        assert_eq!(
+            parsed("l"),
+            Some((Face::L, Direction::Clockwise, Layers::Depth(0), false))
+        );
+        assert_eq!(
+            parsed("x'"),
+            Some((Face::R, Direction::Counterclockwise, Layers::Whole, false))
+        );
+        assert_eq!(
+            parsed("E2"),
+            Some((Face::D, Direction::Half, Layers::Middle, false))
+        );
+        for mv in [
+            "R++",
+            "Rx",
+            "0R",
+            "rw",
+            "xw",
+            "Mw",
+            "2x",
+            "3M",
+            "X",
+            "m",
+            "2",
+            "R'2",
+            "Ŕ",
+            "99999999999999999999R",
+        ] {
+            assert_eq!(parsed(mv), None, "{mv}");
+        }
+
+        assert_eq!(
+            parse_scramble("R U  Rx").unwrap_err(),
+            ScrambleError::Notation {
+                index: 2,
+                found: "Rx".to_string()
+            }
+        );
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        let error = cube.try_apply_scramble("R U 4Rw").unwrap_err();
+        assert_eq!(
+            error,
+            ScrambleError::Layer {
+                index: 2,
+                found: "4Rw".to_string(),
+                layers: 3
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "move '4Rw' at index 2 does not fit a puzzle of 3 layers"
+        );
+        assert_eq!(
+            cube.to_facelets(),
+            Cube::<ThreeByThree>::new().to_facelets()
+        );
+
+        cube.apply_scramble("R U R' U' ?");
+        assert_eq!(
+            cube.to_facelets(),
+            Cube::<ThreeByThree>::new().to_facelets()
+        );
+    }
+
+    #[test]
+    fn rotations_and_slices() {
+        fn same<T: Default>(a: &str, b: &str)
+        where
+            usize: From<T>,
+        {
+            let (mut first, mut second) = (Cube::<T>::new(), Cube::<T>::new());
+            first.try_apply_scramble(a).unwrap();
+            second.try_apply_scramble(b).unwrap();
+            assert_eq!(first.state, second.state, "{a} and {b}");
+        }
+
+        same::<ThreeByThree>("x y' z2", "3Rw 3Uw' 3Fw2");
+        same::<ThreeByThree>("M E' S2", "2L 2D' 2F2");
+        same::<ThreeByThree>("x", "R M' L'");
+        same::<TwoByTwo>("x y z", "Rw Uw Fw");
+        same::<FiveByFive>("M E S", "3L 3D 3F");
+        same::<SevenBySeven>("y", "7Uw");
+
+        let mut cube = Cube::<FourByFour>::new();
+        assert_eq!(
+            cube.try_apply_scramble("R M"),
+            Err(ScrambleError::Layer {
+                index: 1,
+                found: "M".to_string(),
+                layers: 4
+            })
+        );
+    }
+
+    #[test]
+    fn move_tables() {
+        for size in 2..=7 {
+            let mut scrambled = Array3::from_shape_fn((6, size, size), |(f, row, col)| Sticker {
+                face: Face::from(f),
+                row,
+                col,
+                rotation: 0,
+            });
+            for mv in ["R", "U'", "F2", "2L", "Dw", "B'"] {
+                turn(
+                    &mut scrambled,
+                    &parse_move(mv).unwrap().within(size).unwrap(),
+                );
+            }
+
+            for f in 0..6 {
+                for depth in 0..size {
+                    for (wide, dir) in [false, true]
+                        .iter()
+                        .flat_map(|&w| (1..4).map(move |d| (w, d)))
+                    {
+                        let mv = LayerTurn {
+                            face: Face::from(f),
+                            dir: Direction::from(dir),
+                            depth,
+                            wide,
+                        };
+
+                        let mut expected = scrambled.clone();
+                        let times = if mv.dir == Direction::Half { 2 } else { 1 };
+                        let quarter = LayerTurn {
+                            dir: if times == 2 {
+                                Direction::Clockwise
+                            } else {
+                                mv.dir
+                            },
+                            ..mv
+                        };
+                        for _ in 0..times {
+                            turn(&mut expected, &quarter);
+                        }
+
+                        let mut state = scrambled.clone();
+                        let table = move_table(Net::cube(size), &mv).unwrap();
+                        table.apply(stickers(&mut state));
+                        assert_eq!(state, expected, "{mv:?} on {size}x{size}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// A layer turn in cube notation, generated for the property tests.
+    #[derive(Debug, Clone, Copy)]
+    struct Turn {
+        face: usize,
+        /// Layer turned counting from the face, from 1, or the number of layers turned when
+        /// wide.
+        depth: usize,
+        wide: bool,
+        quarter_turns: u8,
+    }
+
+    impl fmt::Display for Turn {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            if self.depth > 1 {
+                write!(f, "{}", self.depth)?;
+            }
+            write!(f, "{}", Face::from(self.face))?;
+            if self.wide {
+                write!(f, "w")?;
+            }
+            match self.quarter_turns {
+                1 => Ok(()),
+                2 => write!(f, "2"),
+                _ => write!(f, "'"),
+            }
+        }
+    }
+
+    impl Turn {
+        fn random(rng: &mut Rand32, size: usize) -> Self {
+            let depth = rng.rand_range(1..size as u32 + 1) as usize;
+            Turn {
+                face: rng.rand_range(0..6) as usize,
+                depth,
+                wide: depth > 1 && rng.rand_range(0..2) == 1,
+                quarter_turns: rng.rand_range(1..4) as u8,
+            }
+        }
+
+        fn inverse(self) -> Self {
+            Turn {
+                quarter_turns: 4 - self.quarter_turns,
+                ..self
+            }
+        }
+
+        /// The same layer turned from the opposite face.
+        fn mirrored(self, size: usize) -> Self {
+            Turn {
+                face: (self.face + 3) % 6,
+                depth: size + 1 - self.depth,
+                ..self.inverse()
+            }
+        }
+
+        /// Every layer of a wide turn, turned on its own.
+        fn layers(self) -> String {
+            let layers: Vec<String> = (1..=self.depth)
+                .map(|depth| {
+                    Turn {
+                        depth,
+                        wide: false,
+                        ..self
+                    }
+                    .to_string()
+                })
+                .collect();
+            layers.join(" ")
+        }
+    }
+
+    fn random_alg(rng: &mut Rand32, size: usize, length: usize) -> Vec<Turn> {
+        (0..length).map(|_| Turn::random(rng, size)).collect()
+    }
+
+    fn alg(turns: &[Turn]) -> String {
+        let turns: Vec<String> = turns.iter().map(Turn::to_string).collect();
+        turns.join(" ")
+    }
+
+    fn inverse_alg(turns: &[Turn]) -> String {
+        let turns: Vec<Turn> = turns.iter().rev().map(|t| t.inverse()).collect();
+        alg(&turns)
+    }
+
+    /// Stickers of a cube with their orientation, to compare states.
+    fn snapshot<T>(cube: &Cube<T>) -> (Array3<Face>, Array3<Sticker>) {
+        (cube.state.clone(), cube.orientation().unwrap().clone())
+    }
+
+    /// Checks that moves on a cube of one size form a group: every move has an inverse and
+    /// quarter turns have order four, whatever state they are made from, and that moves only
+    /// move stickers around.
+    fn move_group<T>(seed: u64)
+    where
+        usize: From<T>,
+        T: Default,
+    {
+        let mut rng = Rand32::new(seed);
+        let size = usize::from(T::default());
+
+        for _ in 0..50 {
+            let scramble = random_alg(&mut rng, size, 25);
+            let turn = Turn::random(&mut rng, size);
+
+            let mut cube = Cube::<T>::new();
+            cube.track_orientation(OrientationMarks::All);
+            cube.try_apply_scramble(&alg(&scramble)).unwrap();
+            let before = snapshot(&cube);
+            let context = format!("{turn} after {}", alg(&scramble));
+
+            cube.try_apply_scramble(&format!("{turn} {}", turn.inverse()))
+                .unwrap();
+            assert_eq!(snapshot(&cube), before, "inverse of {context}");
+
+            let quarter = Turn {
+                quarter_turns: 1,
+                ..turn
+            };
+            cube.try_apply_scramble(&alg(&[quarter; 4])).unwrap();
+            assert_eq!(snapshot(&cube), before, "four times {context}");
+
+            cube.try_apply_scramble(&turn.to_string()).unwrap();
+            let after = snapshot(&cube);
+            for face in 0..6 {
+                let count = cube.state.iter().filter(|f| **f == face).count();
+                assert_eq!(
+                    count,
+                    size * size,
+                    "{} stickers after {context}",
+                    Face::from(face)
+                );
+            }
+
+            if !turn.wide {
+                let other_side = turn.mirrored(size);
+                cube.try_apply_scramble(&format!("{} {other_side}", turn.inverse()))
+                    .unwrap();
+                assert_eq!(snapshot(&cube), after, "other side of {context}");
+            } else {
+                cube.try_apply_scramble(&format!("{} {}", turn.inverse(), turn.layers()))
+                    .unwrap();
+                assert_eq!(snapshot(&cube), after, "layers of {context}");
+            }
+
+            cube.try_apply_scramble(&format!("{} {}", turn.inverse(), inverse_alg(&scramble)))
+                .unwrap();
+            assert_eq!(cube.state, Cube::<T>::new().state, "{context}");
+            assert_home(&cube, &[]);
+        }
+    }
+
+    #[test]
+    fn move_group_properties() {
+        move_group::<TwoByTwo>(2);
+        move_group::<ThreeByThree>(3);
+        move_group::<FourByFour>(4);
+        move_group::<FiveByFive>(5);
+        move_group::<SixBySix>(6);
+        move_group::<SevenBySeven>(7);
+    }
+
     #[test]
     fn two_by_two() {
         {
             let mut cube = Cube::<TwoByTwo>::new();
 
-            cube.apply_scramble("U' R F' R2 F R U2 R' F' U2 R2");
+            cube.apply_scramble(fixtures::TWO_BY_TWO[0]);
 
             assert_eq!(
                 cube.state,
@@ -658,7 +2346,7 @@ mod tests {
         {
             let mut cube = Cube::<TwoByTwo>::new();
 
-            cube.apply_scramble("R' U2 R' F' R U' R F' R' U R'");
+            cube.apply_scramble(fixtures::TWO_BY_TWO[1]);
 
             assert_eq!(
                 cube.state,
@@ -675,7 +2363,7 @@ mod tests {
         {
             let mut cube = Cube::<TwoByTwo>::new();
 
-            cube.apply_scramble("U' R' F U' F' U2 R2 U2 R2 U' F");
+            cube.apply_scramble(fixtures::TWO_BY_TWO[2]);
 
             assert_eq!(
                 cube.state,
@@ -692,7 +2380,7 @@ mod tests {
         {
             let mut cube = Cube::<TwoByTwo>::new();
 
-            cube.apply_scramble("R U' R' U F2 U' F U2 F R U");
+            cube.apply_scramble(fixtures::TWO_BY_TWO[3]);
 
             assert_eq!(
                 cube.state,
@@ -709,7 +2397,7 @@ mod tests {
         {
             let mut cube = Cube::<TwoByTwo>::new();
 
-            cube.apply_scramble("R' U2 F' U' F R' U' F U2 F U'");
+            cube.apply_scramble(fixtures::TWO_BY_TWO[4]);
 
             assert_eq!(
                 cube.state,
@@ -730,7 +2418,7 @@ mod tests {
         {
             let mut cube = Cube::<FourByFour>::new();
 
-            cube.apply_scramble("D2 B2 R2 L B2 D2 R B2 F' L2 D2 R L2 D L' B D L' B2 Rw2 B2 U Rw2 Fw2 Uw2 L' D2 L Fw2 U R F2 B L2 Fw L2 B Uw Rw' F' Rw Fw' U2 Rw2");
+            cube.apply_scramble(fixtures::FOUR_BY_FOUR[0]);
 
             assert_eq!(
                 cube.state,
@@ -747,7 +2435,7 @@ mod tests {
         {
             let mut cube = Cube::<FourByFour>::new();
 
-            cube.apply_scramble("D L B R' F2 R2 D2 L2 D2 R' B2 L F2 D' L' F' U' R' D' R2 Fw2 U2 Rw2 L Fw2 R2 U' R' U2 D' R Uw2 D' Fw' L2 U' Fw R2 Fw2 L' F2 Rw Uw' L2 Fw' Uw");
+            cube.apply_scramble(fixtures::FOUR_BY_FOUR[1]);
 
             assert_eq!(
                 cube.state,
@@ -764,7 +2452,7 @@ mod tests {
         {
             let mut cube = Cube::<FourByFour>::new();
 
-            cube.apply_scramble("F R D2 F2 U2 R2 B2 U2 L2 D L' B' U F2 R' F B2 U' L2 Fw2 R B' Uw2 Rw2 R' U2 R2 B2 D2 Rw2 Fw2 B' Uw' F' Rw2 R2 Uw2 U2 Fw' R2 Uw Fw' Rw2 L2 Uw");
+            cube.apply_scramble(fixtures::FOUR_BY_FOUR[2]);
 
             assert_eq!(
                 cube.state,
@@ -781,7 +2469,7 @@ mod tests {
         {
             let mut cube = Cube::<FourByFour>::new();
 
-            cube.apply_scramble("D2 F2 D F2 R2 D' F2 D2 B2 L R U F' L' U2 F2 L' B' L U2 Rw2 Fw2 U F' B U F' Rw2 D2 F' Uw2 R' U2 Rw F' L' D' B' Uw Rw' Fw U B2 R2 Uw");
+            cube.apply_scramble(fixtures::FOUR_BY_FOUR[3]);
 
             assert_eq!(
                 cube.state,
@@ -798,7 +2486,7 @@ mod tests {
         {
             let mut cube = Cube::<FourByFour>::new();
 
-            cube.apply_scramble("D' F U' L2 D U' L2 U R2 D F' U' R D' L2 F' L D' R' Rw2 U' Rw2 Uw2 Fw2 L' U L' D Rw2 Uw2 D Rw2 Fw U' L2 D Rw' Uw2 Fw2 L2 Fw' R U2");
+            cube.apply_scramble(fixtures::FOUR_BY_FOUR[4]);
 
             assert_eq!(
                 cube.state,
@@ -818,7 +2506,7 @@ mod tests {
         {
             let mut cube = Cube::<FiveByFive>::new();
 
-            cube.apply_scramble("Uw2 Dw2 Lw Rw F2 B' D' F R U Dw2 Uw' Lw' Uw' D F' B2 Bw D2 Dw U2 B F2 Bw U' Rw2 Uw U2 Dw2 B2 Lw' Bw2 Uw' Bw' Rw D2 Dw' U L2 B' D Fw' Lw Fw2 D' Lw2 Fw2 Uw D2 R' Lw L' B Uw' D Rw' L' U2 Rw D'");
+            cube.apply_scramble(fixtures::FIVE_BY_FIVE[0]);
 
             assert_eq!(
                 cube.state,
@@ -871,7 +2559,7 @@ mod tests {
         {
             let mut cube = Cube::<FiveByFive>::new();
 
-            cube.apply_scramble("Lw2 Uw' D2 R2 Lw' B Dw U F' Dw B2 U2 Rw' D2 Uw' Rw D' B2 D Uw2 L Uw2 L' Bw' Rw2 B2 L' Lw' F D2 Rw L Bw L2 Rw2 B2 Dw2 B' U F L2 Uw2 Bw' Uw2 Rw L Fw Rw D' U B' Fw2 Rw2 Lw2 U' Fw Lw D' R2 F'");
+            cube.apply_scramble(fixtures::FIVE_BY_FIVE[1]);
 
             assert_eq!(
                 cube.state,
@@ -923,7 +2611,7 @@ mod tests {
             {
                 let mut cube = Cube::<FiveByFive>::new();
 
-                cube.apply_scramble("Dw F2 Lw2 F2 Bw2 Rw2 Dw' R Rw' Uw Dw' Bw2 Dw2 Lw2 Fw' D2 F2 R2 L Fw' Dw2 L B Fw R' Bw2 B F2 L2 Lw' B2 Uw2 U R2 B' F Bw R' B Dw2 Lw' Uw2 Fw B' Bw' L2 B' Rw' L R' B2 U2 Fw F' L R' Fw2 U' R2 Fw2");
+                cube.apply_scramble(fixtures::FIVE_BY_FIVE[2]);
 
                 assert_eq!(
                     cube.state,
@@ -977,7 +2665,7 @@ mod tests {
         {
             let mut cube = Cube::<FiveByFive>::new();
 
-            cube.apply_scramble("B Fw' Uw' L2 Bw' Fw2 D U2 Rw' R' Lw2 B Rw' Dw' Lw' Bw R2 L2 Rw Fw2 Uw2 D R2 Lw D' Fw Uw' U' R F2 D Dw2 F' B' Fw D2 Fw' B2 Rw R' Lw' Dw R' B' Fw2 Lw2 D2 F2 Fw Lw2 Dw F Bw' B' L2 Rw Fw' B2 L' D2");
+            cube.apply_scramble(fixtures::FIVE_BY_FIVE[3]);
 
             assert_eq!(
                 cube.state,
@@ -1030,7 +2718,7 @@ mod tests {
         {
             let mut cube = Cube::<FiveByFive>::new();
 
-            cube.apply_scramble("F' U2 Fw' B Dw' L' Rw R2 Bw L2 Bw2 Uw' Dw Fw2 F2 U2 Dw2 Uw' F' Dw L Bw' Dw F' Dw' Bw R2 L2 U B2 Dw2 Lw F2 B U Uw Dw F' Lw' R2 Fw Bw2 Uw2 Rw U' R Uw Bw' Lw Bw Fw2 Dw2 B' L' Bw R' Fw2 B Lw2 U'");
+            cube.apply_scramble(fixtures::FIVE_BY_FIVE[4]);
 
             assert_eq!(
                 cube.state,
@@ -1087,7 +2775,7 @@ mod tests {
         {
             let mut cube = Cube::<SixBySix>::new();
 
-            cube.apply_scramble("Fw2 U' Bw' Lw Uw' B L 3Fw2 Dw' 3Fw2 F R' Uw B2 Rw' B' L' U2 Lw Fw Rw Fw2 R' D' Rw 3Rw Uw2 F Rw' Lw2 R2 Dw' Fw2 D2 Lw R' Fw2 D R' 3Fw' Lw' Bw2 D2 3Fw 3Rw' R 3Fw L2 Rw' 3Fw2 B 3Rw2 R' 3Fw2 3Rw2 3Uw' 3Fw' B Rw' 3Rw2 Lw2 Bw Dw R' Lw B2 L F Bw Uw' Fw2 3Fw2 U2 Dw2 Rw 3Uw' 3Fw' 3Uw2 3Fw Dw'");
+            cube.apply_scramble(fixtures::SIX_BY_SIX[0]);
 
             assert_eq!(
                 cube.state,
@@ -1146,7 +2834,7 @@ mod tests {
         {
             let mut cube = Cube::<SixBySix>::new();
 
-            cube.apply_scramble("D' L' B' Fw 3Rw Lw2 Bw L Bw2 D' Bw2 L2 3Fw2 U2 3Fw' Bw2 F R' Dw' R B2 L Lw F Fw2 3Uw2 Dw' Fw' Lw' R D' L2 Bw F2 R2 U D2 R2 L' Rw B2 Rw B' Rw Lw2 Uw2 Lw Uw' B Fw2 Uw Rw' 3Uw Uw' Lw' U F2 Rw2 D Dw F2 Fw' 3Uw2 Uw2 L2 Uw2 F2 U2 R2 3Uw2 Uw' U' F2 Uw Fw' F' D Uw2 Bw2 D");
+            cube.apply_scramble(fixtures::SIX_BY_SIX[1]);
 
             assert_eq!(
                 cube.state,
@@ -1205,7 +2893,7 @@ mod tests {
         {
             let mut cube = Cube::<SixBySix>::new();
 
-            cube.apply_scramble("Rw2 Lw F2 Uw 3Uw R' Fw D2 R2 F2 Bw2 B L' U2 Rw' 3Fw2 L' 3Uw2 B L2 Rw R' 3Fw2 Uw Bw Rw Uw2 L2 R2 3Rw2 3Uw2 L2 B2 Lw' Rw' 3Fw2 Bw B' Fw Rw2 D' F L2 Rw 3Uw' Bw' Lw2 R2 3Fw2 B 3Rw' Lw B' 3Rw' Lw2 F Uw Rw Dw 3Rw2 Rw Uw F' Uw Dw' L' Uw R2 D2 3Fw2 Fw2 B' L' 3Uw Fw' Dw' L Dw' F Bw'");
+            cube.apply_scramble(fixtures::SIX_BY_SIX[2]);
 
             assert_eq!(
                 cube.state,
@@ -1264,7 +2952,7 @@ mod tests {
         {
             let mut cube = Cube::<SixBySix>::new();
 
-            cube.apply_scramble("Lw' Fw' Bw 3Uw' L' B U' Lw' U2 L2 U2 R U2 L F' Bw 3Rw Lw Dw' Uw' B F' 3Rw' Bw' Rw2 Dw' R Fw L' 3Fw2 R 3Rw2 L2 Dw2 3Rw' R2 3Uw2 B' U2 Lw 3Fw' Rw2 Dw U' L' 3Fw2 L U2 L2 3Uw2 Rw 3Rw2 3Fw L Uw2 3Fw2 Dw Uw2 Rw 3Rw2 3Fw2 R' Bw Lw2 Uw2 Dw Lw2 3Rw' 3Uw Bw2 3Rw2 Fw' U2 Lw2 3Rw2 Rw2 Bw2 U' D R", );
+            cube.apply_scramble(fixtures::SIX_BY_SIX[3]);
 
             assert_eq!(
                 cube.state,
@@ -1323,9 +3011,7 @@ mod tests {
         {
             let mut cube = Cube::<SixBySix>::new();
 
-            cube.apply_scramble(
-                "F2 U F' Rw' B2 3Fw2 L' 3Rw' B' Rw F' Fw 3Rw2 3Uw2 R Uw2 Rw2 3Fw2 Bw2 F' U Fw2 L2 U2 Uw Lw Fw2 U' 3Rw Uw2 3Uw2 B Dw D' F2 L' D' L R B2 Dw2 F2 R Fw' R' F2 3Rw' Dw Uw2 Lw Uw2 D' F2 B 3Fw2 Rw2 U2 D L' Fw 3Fw' D' 3Rw' D' Uw U' B Dw' B Fw2 3Rw' 3Uw2 R' U' R2 3Uw D2 Uw 3Fw2 Fw"
-);
+            cube.apply_scramble(fixtures::SIX_BY_SIX[4]);
 
             assert_eq!(
                 cube.state,
@@ -1387,7 +3073,7 @@ mod tests {
         {
             let mut cube = Cube::<SevenBySeven>::new();
 
-            cube.apply_scramble("D 3Fw Bw2 U' Bw R2 3Bw L' 3Uw R Rw2 3Lw' L' Uw' 3Lw2 3Dw2 B2 L Rw' 3Dw B' U2 F' B U 3Rw' R F' Dw' 3Rw' 3Fw Rw2 R2 3Lw' 3Uw 3Rw2 F 3Dw Rw2 Lw' Fw' 3Bw2 U' L' 3Bw2 U2 Uw 3Bw2 Lw' U 3Lw' B Fw2 D2 3Lw' 3Bw Uw2 B D Lw 3Rw2 3Lw' Bw' Fw' 3Fw' 3Lw Dw' 3Uw Rw2 U2 B' Bw Rw' Fw' R2 F 3Rw B2 3Uw Dw' F' 3Lw2 D2 3Lw' 3Uw' Uw2 Rw 3Fw' D' Dw2 U' Fw Bw' 3Uw' Lw2 3Dw' Uw' R Rw 3Rw2");
+            cube.apply_scramble(fixtures::SEVEN_BY_SEVEN[0]);
 
             assert_eq!(
                 cube.state,
@@ -1452,9 +3138,7 @@ mod tests {
         {
             let mut cube = Cube::<SevenBySeven>::new();
 
-            cube.apply_scramble(
-                "D2 Fw2 U2 3Rw 3Dw D' 3Uw' 3Bw Bw' Rw2 D U 3Uw' 3Lw F U 3Lw2 3Dw' 3Bw' 3Dw2 Fw2 3Dw' Bw2 R' Dw' R' 3Rw2 3Bw' Fw2 R2 3Dw2 Bw2 3Rw2 U 3Uw Fw' Uw2 3Uw' 3Dw Fw' Dw2 B Rw' 3Dw' B U' 3Dw2 Fw' D' 3Fw U 3Rw2 D 3Uw Dw 3Dw L2 3Lw Rw 3Rw' Dw' U Rw' L Fw2 3Fw' L' D' R2 U' Bw D' Rw2 L 3Dw' 3Fw' U Lw L Bw Uw2 L' B2 Fw' R' F' 3Dw2 3Lw' U' 3Rw2 F 3Uw' Lw 3Rw2 3Lw D' B2 3Fw D Bw2",
-);
+            cube.apply_scramble(fixtures::SEVEN_BY_SEVEN[1]);
 
             assert_eq!(
                 cube.state,
@@ -1519,9 +3203,7 @@ mod tests {
         {
             let mut cube = Cube::<SevenBySeven>::new();
 
-            cube.apply_scramble(
-                "3Bw2 Rw F Lw' 3Lw' 3Bw 3Dw Uw Bw Fw Rw 3Bw2 F' 3Lw Dw2 3Rw F Rw2 D Fw' U F' Fw 3Uw' Lw' L2 D2 Fw' D' Bw2 Fw Uw U Lw R' 3Rw' Rw' 3Fw' 3Rw' Lw' Dw' L' 3Uw D2 B2 3Rw' F2 D' Lw F2 D Dw2 Uw L2 F' 3Fw' Uw' Rw' F2 Dw2 3Rw 3Dw' R B2 U2 3Rw2 Lw' Uw' 3Bw Fw2 B2 Bw 3Rw Uw' D2 R2 3Lw2 3Dw 3Rw F' Lw Dw Bw 3Bw Dw 3Lw L' B 3Lw' B' 3Dw' Bw2 L' Rw2 D' 3Uw2 Lw B' F Bw2",
-);
+            cube.apply_scramble(fixtures::SEVEN_BY_SEVEN[2]);
 
             assert_eq!(
                 cube.state,
@@ -1586,9 +3268,7 @@ mod tests {
         {
             let mut cube = Cube::<SevenBySeven>::new();
 
-            cube.apply_scramble(
-                "3Rw B' Uw' Dw F2 Rw' Fw2 B' Dw 3Rw' U' 3Lw2 3Dw2 3Uw2 Uw' Rw2 D L' U2 Lw2 3Dw Fw2 3Rw' Rw2 3Fw 3Dw2 3Rw' Dw2 U' 3Lw' F D Rw 3Dw' 3Fw 3Dw Bw' Lw F' Dw F2 L Uw2 Rw2 Lw Fw' F B' U Dw 3Dw2 D' 3Rw' Dw2 Lw' Rw 3Bw Dw' Rw B U' Rw2 B 3Rw' Bw2 Rw2 L B2 U' R' Fw Dw' D 3Dw' Rw' Dw D Rw2 Fw' 3Bw 3Fw2 Rw 3Uw' U' 3Rw 3Uw F' 3Lw' U2 3Rw' F2 R 3Rw Dw F B' Dw2 F2 Rw2 3Rw'",
-);
+            cube.apply_scramble(fixtures::SEVEN_BY_SEVEN[3]);
 
             assert_eq!(
                 cube.state,
@@ -1653,9 +3333,7 @@ mod tests {
         {
             let mut cube = Cube::<SevenBySeven>::new();
 
-            cube.apply_scramble(
-                "3Lw2 Fw 3Rw2 Dw' Uw R' Dw2 Bw2 Uw Dw Lw2 Fw' L Bw 3Rw 3Uw2 Uw' F' U 3Uw' 3Rw2 3Dw' 3Bw2 Dw L Lw D Fw' 3Uw2 Fw' Dw' 3Dw2 Rw2 F' Lw Rw Fw' 3Rw' 3Fw' Rw2 B' 3Uw 3Dw' Bw2 Fw2 U2 F L' U2 B' Bw Fw2 3Uw D' F' 3Lw' R2 3Bw2 Fw' 3Rw' Dw' U' 3Dw2 Rw2 3Dw' 3Uw 3Rw' Fw2 3Uw' L 3Rw' Bw 3Bw Dw2 Fw R2 D2 3Rw' 3Dw B Fw Uw F2 Uw2 3Bw' Dw' 3Dw' 3Bw R2 3Bw 3Rw' Uw2 R2 3Uw2 U2 3Dw2 3Lw 3Rw' U2 Rw"
-);
+            cube.apply_scramble(fixtures::SEVEN_BY_SEVEN[4]);
 
             assert_eq!(
                 cube.state,
@@ -0,0 +1,527 @@
+use crate::puzzles::cube::{
+    nth_move, parse_scramble_within, Direction, Face, LayerTurn, DEFAULT_COLOR_SCHEME,
+};
+use crate::puzzles::metrics::MoveCount;
+use crate::puzzles::net::{self, face_colors, move_table, Net, Painter};
+use crate::puzzles::render::{document, palette, patterns};
+#[cfg(feature = "serde")]
+use crate::puzzles::StateError;
+use crate::puzzles::{state_json, ColorSchemes, Puzzle, RenderOptions, ScrambleError};
+use crate::utils::color::Color;
+use ndarray::Array2;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use svg::node::element::{Group, SVG};
+use svg::Node;
+
+/// A cuboid such as the 2x2x3 or the 3x3x2, turned with cube notation.
+///
+/// Faces are no longer all the same shape, so quarter turns are only allowed on square faces:
+/// any other face can only be turned by half turns. Cuboids are turned and drawn as cubes are,
+/// only their faces are stored apart since they are not all the same size.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "CuboidData",
+        bound(deserialize = "(usize, usize, usize): From<T>, T: Default")
+    )
+)]
+pub struct Cuboid<T> {
+    /// Stickers of each face, indexed by [`Face`] and laid out as in the net.
+    pub state: [Array2<Face>; 6],
+    width: usize,
+    depth: usize,
+    height: usize,
+    color_scheme: HashMap<Face, Color>,
+    cubie_size: f64,
+    gap: f64,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Debug, Default)]
+pub struct TwoByTwoByThree {}
+#[derive(Debug, Default)]
+pub struct ThreeByThreeByTwo {}
+#[derive(Debug, Default)]
+pub struct ThreeByThreeByFour {}
+
+/// Dimensions are given in notation order: width, depth and height.
+impl From<TwoByTwoByThree> for (usize, usize, usize) {
+    fn from(_: TwoByTwoByThree) -> Self {
+        (2, 2, 3)
+    }
+}
+impl From<ThreeByThreeByTwo> for (usize, usize, usize) {
+    fn from(_: ThreeByThreeByTwo) -> Self {
+        (3, 3, 2)
+    }
+}
+impl From<ThreeByThreeByFour> for (usize, usize, usize) {
+    fn from(_: ThreeByThreeByFour) -> Self {
+        (3, 3, 4)
+    }
+}
+
+/// A cuboid as it is deserialized, before checking that it is one of its size.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CuboidData {
+    state: [Array2<Face>; 6],
+    width: usize,
+    depth: usize,
+    height: usize,
+    color_scheme: HashMap<Face, Color>,
+    cubie_size: f64,
+    gap: f64,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<CuboidData> for Cuboid<T>
+where
+    (usize, usize, usize): From<T>,
+    T: Default,
+{
+    type Error = StateError;
+
+    fn try_from(data: CuboidData) -> Result<Self, Self::Error> {
+        let cuboid = Cuboid::<T>::new();
+        let found = (data.width, data.depth, data.height);
+        let expected = (cuboid.width, cuboid.depth, cuboid.height);
+        if found != expected {
+            return Err(StateError::Shape { expected, found });
+        }
+
+        for (i, stickers) in data.state.iter().enumerate() {
+            let face = Face::from(i);
+            let expected = cuboid.state[i].dim();
+            if stickers.dim() != expected {
+                return Err(StateError::FaceShape {
+                    face: face.facelet(),
+                    expected,
+                    found: stickers.dim(),
+                });
+            }
+
+            let found = data.state.iter().flatten().filter(|f| **f == face).count();
+            if found != stickers.len() {
+                return Err(StateError::StickerCount {
+                    face: face.facelet(),
+                    expected: stickers.len(),
+                    found,
+                });
+            }
+        }
+
+        Ok(Cuboid {
+            state: data.state,
+            color_scheme: data.color_scheme,
+            cubie_size: data.cubie_size,
+            gap: data.gap,
+            ..cuboid
+        })
+    }
+}
+
+impl<T> Puzzle for Cuboid<T>
+where
+    (usize, usize, usize): From<T>,
+    T: Default,
+{
+    fn new() -> Self {
+        let (width, depth, height) = T::into(T::default());
+        let net = Net {
+            width,
+            depth,
+            height,
+        };
+
+        let state = [0, 1, 2, 3, 4, 5].map(|i| {
+            let face = Face::from(i);
+            Array2::from_elem(net.shape(face), face)
+        });
+
+        Cuboid {
+            state,
+            width,
+            depth,
+            height,
+            color_scheme: DEFAULT_COLOR_SCHEME.clone(),
+            cubie_size: 10.,
+            gap: 2.,
+            _marker: Default::default(),
+        }
+    }
+
+    fn try_apply_scramble(&mut self, scramble: &str) -> Result<(), ScrambleError> {
+        let net = self.net();
+        let moves = self.parse_scramble(scramble)?;
+
+        let mut stickers: Vec<Face> = self.state.iter().flatten().copied().collect();
+        for table in moves.iter().filter_map(|mv| move_table(net, mv)) {
+            table.apply(&mut stickers);
+        }
+        let mut stickers = stickers.into_iter();
+        for sticker in self.state.iter_mut().flatten() {
+            *sticker = stickers.next().expect("moves keep every sticker");
+        }
+        Ok(())
+    }
+
+    fn move_count(&self, alg: &str) -> Result<MoveCount, ScrambleError> {
+        let net = self.net();
+        Ok(MoveCount::moves(&self.parse_scramble(alg)?, |face| {
+            net.layers(face)
+        }))
+    }
+
+    fn get_default_color_scheme(&self) -> ColorSchemes {
+        ColorSchemes::Cube(DEFAULT_COLOR_SCHEME.clone())
+    }
+
+    fn to_facelets(&self) -> String {
+        [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B]
+            .iter()
+            .flat_map(|f| self.state[*f as usize].iter())
+            .map(Face::to_string)
+            .collect()
+    }
+
+    fn to_json(&self) -> String {
+        let faces: Vec<(String, Vec<String>)> =
+            [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B]
+                .iter()
+                .map(|f| {
+                    let stickers = self.state[*f as usize].iter();
+                    (f.to_string(), stickers.map(Face::to_string).collect())
+                })
+                .collect();
+
+        let size = format!("[{},{},{}]", self.width, self.depth, self.height);
+        state_json("cuboid", Some(&size), &faces)
+    }
+
+    fn draw_with(&self, options: &RenderOptions) -> SVG {
+        let (width, height) = self.get_preferred_size(options);
+
+        let title = format!("{}x{}x{} cuboid", self.width, self.depth, self.height);
+        let mut svg = document(width, height, options, &title, || self.describe());
+        self.draw_cuboid(&mut svg, options);
+        svg
+    }
+}
+
+impl<T> Cuboid<T> {
+    fn net(&self) -> Net {
+        Net {
+            width: self.width,
+            depth: self.depth,
+            height: self.height,
+        }
+    }
+
+    /// Reads every move of a scramble as it is made on the cuboid, with quarter turns on square
+    /// faces only.
+    fn parse_scramble(&self, scramble: &str) -> Result<Vec<LayerTurn>, ScrambleError> {
+        let net = self.net();
+        let moves = parse_scramble_within(scramble, |face| net.layers(face))?;
+        for (index, mv) in moves.iter().enumerate() {
+            let (rows, cols) = net.shape(mv.face);
+            if rows != cols && matches!(mv.dir, Direction::Clockwise | Direction::Counterclockwise)
+            {
+                return Err(ScrambleError::QuarterTurn {
+                    index,
+                    found: nth_move(scramble, index),
+                });
+            }
+        }
+        Ok(moves)
+    }
+
+    /// Sticker size and gap between faces, from the options or the cuboid's own.
+    fn layout(&self, options: &RenderOptions) -> (f64, f64) {
+        net::layout(options, self.cubie_size, self.gap)
+    }
+
+    /// The stickers of each face row by row, named by the face they belong on, for screen
+    /// readers.
+    fn describe(&self) -> String {
+        let faces: Vec<String> = [Face::U, Face::R, Face::F, Face::D, Face::L, Face::B]
+            .iter()
+            .map(|&face| {
+                let rows: Vec<String> = self.state[face as usize]
+                    .rows()
+                    .into_iter()
+                    .map(|row| {
+                        let stickers: Vec<String> = row.iter().map(Face::to_string).collect();
+                        stickers.join(" ")
+                    })
+                    .collect();
+                format!("{} face: {}.", face, rows.join(", "))
+            })
+            .collect();
+        faces.join(" ")
+    }
+
+    fn draw_cuboid(&self, svg: &mut SVG, options: &RenderOptions) {
+        let (cubie_size, gap) = self.layout(options);
+        if let Some(defs) = patterns(
+            "cuboid",
+            face_colors(&self.color_scheme, options),
+            cubie_size / 4.,
+            options,
+        ) {
+            svg.append(defs);
+        }
+        let colors = face_colors(&self.color_scheme, options).map(|(_, _, color)| color);
+        if let Some(style) = palette(colors, options) {
+            svg.append(style);
+        }
+
+        let mut g = Group::new().set("transform", "translate(0.5,0.5)");
+        let painter = Painter {
+            puzzle: "cuboid",
+            sticker_size: cubie_size,
+            options,
+        };
+        let color_scheme = options.cube_colors.as_ref().unwrap_or(&self.color_scheme);
+        for (x, y, face) in self.net().positions(cubie_size, gap) {
+            let stickers = &self.state[face as usize];
+            painter.face(
+                &mut g,
+                (x, y),
+                stickers.dim(),
+                |row, col| {
+                    let f = stickers[[row, col]];
+                    (*color_scheme.get(&f).unwrap_or(&Color::black()), Some(f))
+                },
+                |_, _, _, _, _| {},
+            );
+        }
+
+        svg.append(g);
+    }
+
+    fn get_preferred_size(&self, options: &RenderOptions) -> (f64, f64) {
+        let (cubie_size, gap) = self.layout(options);
+        self.net().size(cubie_size, gap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::alg::inverse;
+    use crate::puzzles::cube::{Cube, FourByFour, ThreeByThree};
+    use crate::puzzles::fixtures;
+
+    #[derive(Debug, Default)]
+    struct ThreeByThreeByThree {}
+    impl From<ThreeByThreeByThree> for (usize, usize, usize) {
+        fn from(_: ThreeByThreeByThree) -> Self {
+            (3, 3, 3)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct FourByFourByFour {}
+    impl From<FourByFourByFour> for (usize, usize, usize) {
+        fn from(_: FourByFourByFour) -> Self {
+            (4, 4, 4)
+        }
+    }
+
+    fn faces<T>(cuboid: &Cuboid<T>) -> Vec<Face> {
+        cuboid
+            .state
+            .iter()
+            .flat_map(|f| f.iter().copied())
+            .collect()
+    }
+
+    #[test]
+    fn same_as_cube() {
+        let scramble = "U F' U2 F R2 B' U2 L2 R2 F D2 R2 U' B2 U' R B' F' L D2 U";
+
+        let mut cuboid = Cuboid::<ThreeByThreeByThree>::new();
+        let mut cube = Cube::<ThreeByThree>::new();
+        cuboid.apply_scramble(scramble);
+        cube.apply_scramble(scramble);
+
+        assert_eq!(
+            faces(&cuboid),
+            cube.state.iter().copied().collect::<Vec<_>>()
+        );
+
+        let scramble = "Uw2 Rw' F Bw2 D' Lw Fw' U2 Dw B' Rw2 L";
+
+        let mut cuboid = Cuboid::<FourByFourByFour>::new();
+        let mut cube = Cube::<FourByFour>::new();
+        cuboid.apply_scramble(scramble);
+        cube.apply_scramble(scramble);
+
+        assert_eq!(
+            faces(&cuboid),
+            cube.state.iter().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn three_by_three_by_two() {
+        let mut cuboid = Cuboid::<ThreeByThreeByTwo>::new();
+        cuboid.apply_scramble(fixtures::THREE_BY_THREE_BY_TWO[0]);
+
+        assert_eq!(cuboid.state[Face::U as usize].dim(), (3, 3));
+        assert_eq!(cuboid.state[Face::R as usize].dim(), (2, 3));
+        assert_eq!(cuboid.state[Face::F as usize].dim(), (2, 3));
+
+        for face in 0..6 {
+            let face = Face::from(face);
+            let count = faces(&cuboid).iter().filter(|f| **f == face).count();
+            assert_eq!(count, cuboid.state[face as usize].len());
+        }
+
+        let mut r2 = Cuboid::<ThreeByThreeByTwo>::new();
+        r2.apply_scramble("R2");
+        assert_eq!(
+            r2.to_facelets(),
+            "UUDUUDUUDRRRRRRFFBFFBDDUDDUDDULLLLLLFBBFBB"
+        );
+
+        cuboid.apply_scramble("R2 U2 B2 L2 D F2 U' R2");
+        assert_eq!(faces(&cuboid), faces(&Cuboid::<ThreeByThreeByTwo>::new()));
+
+        let svg = cuboid.draw().to_string();
+        assert_eq!(svg.matches("<rect").count(), 42);
+        assert!(svg.contains("viewBox=\"0 0 130 88\""));
+    }
+
+    #[test]
+    fn two_by_two_by_three() {
+        let mut cuboid = Cuboid::<TwoByTwoByThree>::new();
+        cuboid.apply_scramble(fixtures::TWO_BY_TWO_BY_THREE[0]);
+
+        assert_eq!(cuboid.state[Face::F as usize].dim(), (3, 2));
+        assert!(cuboid
+            .to_json()
+            .starts_with("{\"puzzle\":\"cuboid\",\"size\":[2,2,3],"));
+
+        cuboid.apply_scramble("B2 Uw2 F2 2D' U R2 U'");
+        assert_eq!(faces(&cuboid), faces(&Cuboid::<TwoByTwoByThree>::new()));
+    }
+
+    #[test]
+    fn three_by_three_by_four() {
+        let mut cuboid = Cuboid::<ThreeByThreeByFour>::new();
+        cuboid.apply_scramble(fixtures::THREE_BY_THREE_BY_FOUR[0]);
+
+        assert_eq!(cuboid.state[Face::R as usize].dim(), (4, 3));
+        for face in 0..6 {
+            let face = Face::from(face);
+            let count = faces(&cuboid).iter().filter(|f| **f == face).count();
+            assert_eq!(count, cuboid.state[face as usize].len());
+        }
+        assert_eq!(
+            cuboid.to_facelets(),
+            "UUDDDDDDDLLRRFRFRBFRFRFFLBBLBRRBRUUUUUDDUULBBFRFBLFLLBFFBBFRLLLLRB"
+        );
+
+        let inverted: Vec<String> = fixtures::THREE_BY_THREE_BY_FOUR[0]
+            .split_ascii_whitespace()
+            .rev()
+            .map(inverse)
+            .collect();
+        cuboid.apply_scramble(&inverted.join(" "));
+        assert_eq!(faces(&cuboid), faces(&Cuboid::<ThreeByThreeByFour>::new()));
+    }
+
+    #[test]
+    fn rotations() {
+        fn same<T: Default>(a: &str, b: &str)
+        where
+            (usize, usize, usize): From<T>,
+        {
+            let (mut first, mut second) = (Cuboid::<T>::new(), Cuboid::<T>::new());
+            first.try_apply_scramble(a).unwrap();
+            second.try_apply_scramble(b).unwrap();
+            assert_eq!(faces(&first), faces(&second), "{a} and {b}");
+        }
+
+        same::<ThreeByThreeByTwo>("2U", "D'");
+        same::<ThreeByThreeByTwo>("y' R2", "F2 U' D");
+        same::<ThreeByThreeByFour>("x2", "R2 2R2 L2");
+        same::<ThreeByThreeByFour>("z2 U2", "D2 3Fw2");
+        same::<TwoByTwoByThree>("y", "3Uw");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        use serde_json::{json, Value};
+
+        let mut cuboid = Cuboid::<ThreeByThreeByTwo>::new();
+        cuboid.apply_scramble("R2 U F2 D'");
+        let json: Value = serde_json::to_value(&cuboid).unwrap();
+        let de: Cuboid<ThreeByThreeByTwo> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(faces(&de), faces(&cuboid));
+
+        let error = |change: &dyn Fn(&mut Value)| {
+            let mut json = json.clone();
+            change(&mut json);
+            serde_json::from_value::<Cuboid<ThreeByThreeByTwo>>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(&|j| j["height"] = json!(3)),
+            "state has shape (3, 3, 3), expected (3, 3, 2)"
+        );
+        assert_eq!(
+            error(&|j| j["state"][0]["dim"] = json!([3, 2])),
+            "face R has shape (3, 2), expected (2, 3)"
+        );
+        assert_eq!(
+            error(&|j| j["state"][1]["data"][0] = json!("F")),
+            "face U should have 9 stickers, found 8"
+        );
+    }
+
+    #[test]
+    fn quarter_turn_on_rectangle() {
+        let mut cuboid = Cuboid::<ThreeByThreeByFour>::new();
+        assert_eq!(
+            cuboid.try_apply_scramble("U R"),
+            Err(ScrambleError::QuarterTurn {
+                index: 1,
+                found: "R".to_string()
+            })
+        );
+        assert_eq!(faces(&cuboid), faces(&Cuboid::<ThreeByThreeByFour>::new()));
+
+        assert_eq!(
+            cuboid.try_apply_scramble("U2 5U"),
+            Err(ScrambleError::Layer {
+                index: 1,
+                found: "5U".to_string(),
+                layers: 4
+            })
+        );
+        assert_eq!(
+            cuboid.try_apply_scramble("4F2"),
+            Err(ScrambleError::Layer {
+                index: 0,
+                found: "4F2".to_string(),
+                layers: 3
+            })
+        );
+        assert_eq!(
+            cuboid.try_apply_scramble("y x"),
+            Err(ScrambleError::QuarterTurn {
+                index: 1,
+                found: "x".to_string()
+            })
+        );
+        assert!(cuboid.try_apply_scramble("y x2 M2").is_ok());
+    }
+}
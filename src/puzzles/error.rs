@@ -0,0 +1,249 @@
+use std::error::Error;
+use std::fmt;
+
+/// Reasons a puzzle state can be rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateError {
+    /// The facelet string does not have one character per sticker.
+    Length { expected: usize, found: usize },
+    /// A character of the facelet string does not name a face.
+    Facelet { index: usize, found: char },
+    /// A face color is not on exactly as many stickers as a face has.
+    StickerCount {
+        face: char,
+        expected: usize,
+        found: usize,
+    },
+    /// The centers cannot be on a physical puzzle together.
+    Centers,
+    /// The stickers at a corner position do not make up a corner piece.
+    Corner { position: &'static str },
+    /// The stickers at an edge position do not make up an edge piece.
+    Edge { position: &'static str },
+    /// A corner piece is at two positions.
+    DuplicateCorner { position: &'static str },
+    /// An edge piece is at two positions.
+    DuplicateEdge { position: &'static str },
+    /// A single corner is twisted.
+    CornerTwist,
+    /// A single edge is flipped.
+    EdgeFlip,
+    /// Two pieces are swapped.
+    Parity,
+    /// The puzzle does not have as many layers as its type.
+    Size { expected: usize, found: usize },
+    /// The state does not have one sticker per sticker of the puzzle.
+    Shape {
+        expected: (usize, usize, usize),
+        found: (usize, usize, usize),
+    },
+    /// A face does not have the rows and columns of stickers it has on the puzzle.
+    FaceShape {
+        face: char,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The tracked orientation does not have one sticker per sticker of the puzzle, or gives
+    /// positions outside of it.
+    Orientation,
+    /// A lettering scheme does not have one label per sticker it letters.
+    Lettering { expected: usize, found: usize },
+    /// A custom mask does not have one entry per sticker of the puzzle.
+    MaskShape {
+        expected: (usize, usize, usize),
+        found: (usize, usize, usize),
+    },
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateError::Length { expected, found } => {
+                write!(f, "expected {expected} facelets, found {found}")
+            }
+            StateError::Facelet { index, found } => {
+                write!(f, "invalid facelet '{found}' at index {index}")
+            }
+            StateError::StickerCount {
+                face,
+                expected,
+                found,
+            } => write!(
+                f,
+                "face {face} should have {expected} stickers, found {found}"
+            ),
+            StateError::Centers => write!(f, "centers are not in a valid orientation"),
+            StateError::Corner { position } => {
+                write!(f, "stickers at {position} do not form a corner")
+            }
+            StateError::Edge { position } => {
+                write!(f, "stickers at {position} do not form an edge")
+            }
+            StateError::DuplicateCorner { position } => {
+                write!(f, "corner at {position} appears twice")
+            }
+            StateError::DuplicateEdge { position } => {
+                write!(f, "edge at {position} appears twice")
+            }
+            StateError::CornerTwist => write!(f, "a corner is twisted"),
+            StateError::EdgeFlip => write!(f, "an edge is flipped"),
+            StateError::Parity => write!(f, "two pieces are swapped"),
+            StateError::Size { expected, found } => {
+                write!(f, "expected {expected} layers, found {found}")
+            }
+            StateError::Shape { expected, found } => {
+                write!(f, "state has shape {found:?}, expected {expected:?}")
+            }
+            StateError::FaceShape {
+                face,
+                expected,
+                found,
+            } => write!(f, "face {face} has shape {found:?}, expected {expected:?}"),
+            StateError::Orientation => {
+                write!(f, "tracked orientation does not match the puzzle")
+            }
+            StateError::Lettering { expected, found } => {
+                write!(f, "lettering has {found} labels, expected {expected}")
+            }
+            StateError::MaskShape { expected, found } => {
+                write!(f, "mask has shape {found:?}, expected {expected:?}")
+            }
+        }
+    }
+}
+
+impl Error for StateError {}
+
+/// Reasons a scramble can be rejected, with the index of the first move at fault counting
+/// from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrambleError {
+    /// A move is not written in the notation of the puzzle.
+    Notation { index: usize, found: String },
+    /// A move turns layers the puzzle does not have along its axis.
+    Layer {
+        index: usize,
+        found: String,
+        layers: usize,
+    },
+    /// A move turns a rectangular face a quarter, which would jam the puzzle.
+    QuarterTurn { index: usize, found: String },
+}
+
+impl ScrambleError {
+    /// Index of the move at fault.
+    pub fn index(&self) -> usize {
+        match self {
+            ScrambleError::Notation { index, .. }
+            | ScrambleError::Layer { index, .. }
+            | ScrambleError::QuarterTurn { index, .. } => *index,
+        }
+    }
+}
+
+impl ScrambleError {
+    /// The same error, for a move `by` moves further into a longer scramble.
+    pub(crate) fn offset(self, by: usize) -> ScrambleError {
+        match self {
+            ScrambleError::Notation { index, found } => ScrambleError::Notation {
+                index: index + by,
+                found,
+            },
+            ScrambleError::Layer {
+                index,
+                found,
+                layers,
+            } => ScrambleError::Layer {
+                index: index + by,
+                found,
+                layers,
+            },
+            ScrambleError::QuarterTurn { index, found } => ScrambleError::QuarterTurn {
+                index: index + by,
+                found,
+            },
+        }
+    }
+}
+
+impl fmt::Display for ScrambleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScrambleError::Notation { index, found } => {
+                write!(f, "invalid move '{found}' at index {index}")
+            }
+            ScrambleError::Layer {
+                index,
+                found,
+                layers,
+            } => write!(
+                f,
+                "move '{found}' at index {index} does not fit a puzzle of {layers} layers"
+            ),
+            ScrambleError::QuarterTurn { index, found } => write!(
+                f,
+                "move '{found}' at index {index} turns a rectangular face a quarter"
+            ),
+        }
+    }
+}
+
+impl Error for ScrambleError {}
+
+/// Reasons an algorithm can be rejected, with the index of the character at fault counting
+/// from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlgError {
+    /// A bracket, comma, colon or slash is where it cannot be.
+    Unexpected { index: usize, found: char },
+    /// A bracket is opened but never closed.
+    Unclosed { index: usize, found: char },
+    /// A bracket is inside more brackets than an algorithm can have.
+    Nesting { index: usize },
+    /// A group takes the moves its groups expand to over what an algorithm can have.
+    Length { index: usize },
+}
+
+impl fmt::Display for AlgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlgError::Unexpected { index, found } => {
+                write!(f, "unexpected '{found}' at index {index}")
+            }
+            AlgError::Unclosed { index, found } => {
+                write!(f, "'{found}' at index {index} is never closed")
+            }
+            AlgError::Nesting { index } => {
+                write!(f, "bracket at index {index} is nested too deep")
+            }
+            AlgError::Length { index } => {
+                write!(f, "group at index {index} expands to too many moves")
+            }
+        }
+    }
+}
+
+impl Error for AlgError {}
+
+/// Reasons a scramble sheet cannot be made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SheetError {
+    /// No event is registered under the id.
+    Event { found: String },
+    /// A scramble cannot be applied, numbered as on the sheet such as `3` or `E1`.
+    Scramble {
+        number: String,
+        error: ScrambleError,
+    },
+}
+
+impl fmt::Display for SheetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SheetError::Event { found } => write!(f, "no event '{found}'"),
+            SheetError::Scramble { number, error } => write!(f, "scramble {number}: {error}"),
+        }
+    }
+}
+
+impl Error for SheetError {}
@@ -0,0 +1,106 @@
+//! Scrambles applied by the tests of each puzzle, which check the states they lead to, and
+//! measured by the benchmarks. `benches/scramble.rs` includes this file as it is, so it only
+//! holds string literals.
+
+/// Scrambles of the 2x2, see `cube::tests::two_by_two`.
+pub(crate) const TWO_BY_TWO: &[&str] = &[
+    "U' R F' R2 F R U2 R' F' U2 R2",
+    "R' U2 R' F' R U' R F' R' U R'",
+    "U' R' F U' F' U2 R2 U2 R2 U' F",
+    "R U' R' U F2 U' F U2 F R U",
+    "R' U2 F' U' F R' U' F U2 F U'",
+];
+
+/// Scrambles of the 3x3, see `cube::tests::three_by_three`.
+pub(crate) const THREE_BY_THREE: &[&str] = &[
+    "U F' U2 F R2 B' U2 L2 R2 F D2 R2 U' B2 U' R B' F' L D2 U",
+    "B F2 U2 B2 F2 R U2 L2 R U' B D2 L' D' L2 U2 B D' F2",
+    "F U R U2 B U2 B' D2 B L2 D2 L2 D F U L' D' L B2",
+    "U2 R F2 L D2 B2 F2 L2 U' L' U2 L2 D F U B' L2 U F2",
+    "U' L2 U L' U2 D' F' B' R2 F' R B L2 U2 B D2 F2",
+];
+
+/// Scrambles of the 4x4, see `cube::tests::four_by_four`.
+pub(crate) const FOUR_BY_FOUR: &[&str] = &[
+    "D2 B2 R2 L B2 D2 R B2 F' L2 D2 R L2 D L' B D L' B2 Rw2 B2 U Rw2 Fw2 Uw2 L' D2 L Fw2 U R F2 B L2 Fw L2 B Uw Rw' F' Rw Fw' U2 Rw2",
+    "D L B R' F2 R2 D2 L2 D2 R' B2 L F2 D' L' F' U' R' D' R2 Fw2 U2 Rw2 L Fw2 R2 U' R' U2 D' R Uw2 D' Fw' L2 U' Fw R2 Fw2 L' F2 Rw Uw' L2 Fw' Uw",
+    "F R D2 F2 U2 R2 B2 U2 L2 D L' B' U F2 R' F B2 U' L2 Fw2 R B' Uw2 Rw2 R' U2 R2 B2 D2 Rw2 Fw2 B' Uw' F' Rw2 R2 Uw2 U2 Fw' R2 Uw Fw' Rw2 L2 Uw",
+    "D2 F2 D F2 R2 D' F2 D2 B2 L R U F' L' U2 F2 L' B' L U2 Rw2 Fw2 U F' B U F' Rw2 D2 F' Uw2 R' U2 Rw F' L' D' B' Uw Rw' Fw U B2 R2 Uw",
+    "D' F U' L2 D U' L2 U R2 D F' U' R D' L2 F' L D' R' Rw2 U' Rw2 Uw2 Fw2 L' U L' D Rw2 Uw2 D Rw2 Fw U' L2 D Rw' Uw2 Fw2 L2 Fw' R U2",
+];
+
+/// Scrambles of the 5x5, see `cube::tests::five_by_five`.
+pub(crate) const FIVE_BY_FIVE: &[&str] = &[
+    "Uw2 Dw2 Lw Rw F2 B' D' F R U Dw2 Uw' Lw' Uw' D F' B2 Bw D2 Dw U2 B F2 Bw U' Rw2 Uw U2 Dw2 B2 Lw' Bw2 Uw' Bw' Rw D2 Dw' U L2 B' D Fw' Lw Fw2 D' Lw2 Fw2 Uw D2 R' Lw L' B Uw' D Rw' L' U2 Rw D'",
+    "Lw2 Uw' D2 R2 Lw' B Dw U F' Dw B2 U2 Rw' D2 Uw' Rw D' B2 D Uw2 L Uw2 L' Bw' Rw2 B2 L' Lw' F D2 Rw L Bw L2 Rw2 B2 Dw2 B' U F L2 Uw2 Bw' Uw2 Rw L Fw Rw D' U B' Fw2 Rw2 Lw2 U' Fw Lw D' R2 F'",
+    "Dw F2 Lw2 F2 Bw2 Rw2 Dw' R Rw' Uw Dw' Bw2 Dw2 Lw2 Fw' D2 F2 R2 L Fw' Dw2 L B Fw R' Bw2 B F2 L2 Lw' B2 Uw2 U R2 B' F Bw R' B Dw2 Lw' Uw2 Fw B' Bw' L2 B' Rw' L R' B2 U2 Fw F' L R' Fw2 U' R2 Fw2",
+    "B Fw' Uw' L2 Bw' Fw2 D U2 Rw' R' Lw2 B Rw' Dw' Lw' Bw R2 L2 Rw Fw2 Uw2 D R2 Lw D' Fw Uw' U' R F2 D Dw2 F' B' Fw D2 Fw' B2 Rw R' Lw' Dw R' B' Fw2 Lw2 D2 F2 Fw Lw2 Dw F Bw' B' L2 Rw Fw' B2 L' D2",
+    "F' U2 Fw' B Dw' L' Rw R2 Bw L2 Bw2 Uw' Dw Fw2 F2 U2 Dw2 Uw' F' Dw L Bw' Dw F' Dw' Bw R2 L2 U B2 Dw2 Lw F2 B U Uw Dw F' Lw' R2 Fw Bw2 Uw2 Rw U' R Uw Bw' Lw Bw Fw2 Dw2 B' L' Bw R' Fw2 B Lw2 U'",
+];
+
+/// Scrambles of the 6x6, see `cube::tests::six_by_six`.
+pub(crate) const SIX_BY_SIX: &[&str] = &[
+    "Fw2 U' Bw' Lw Uw' B L 3Fw2 Dw' 3Fw2 F R' Uw B2 Rw' B' L' U2 Lw Fw Rw Fw2 R' D' Rw 3Rw Uw2 F Rw' Lw2 R2 Dw' Fw2 D2 Lw R' Fw2 D R' 3Fw' Lw' Bw2 D2 3Fw 3Rw' R 3Fw L2 Rw' 3Fw2 B 3Rw2 R' 3Fw2 3Rw2 3Uw' 3Fw' B Rw' 3Rw2 Lw2 Bw Dw R' Lw B2 L F Bw Uw' Fw2 3Fw2 U2 Dw2 Rw 3Uw' 3Fw' 3Uw2 3Fw Dw'",
+    "D' L' B' Fw 3Rw Lw2 Bw L Bw2 D' Bw2 L2 3Fw2 U2 3Fw' Bw2 F R' Dw' R B2 L Lw F Fw2 3Uw2 Dw' Fw' Lw' R D' L2 Bw F2 R2 U D2 R2 L' Rw B2 Rw B' Rw Lw2 Uw2 Lw Uw' B Fw2 Uw Rw' 3Uw Uw' Lw' U F2 Rw2 D Dw F2 Fw' 3Uw2 Uw2 L2 Uw2 F2 U2 R2 3Uw2 Uw' U' F2 Uw Fw' F' D Uw2 Bw2 D",
+    "Rw2 Lw F2 Uw 3Uw R' Fw D2 R2 F2 Bw2 B L' U2 Rw' 3Fw2 L' 3Uw2 B L2 Rw R' 3Fw2 Uw Bw Rw Uw2 L2 R2 3Rw2 3Uw2 L2 B2 Lw' Rw' 3Fw2 Bw B' Fw Rw2 D' F L2 Rw 3Uw' Bw' Lw2 R2 3Fw2 B 3Rw' Lw B' 3Rw' Lw2 F Uw Rw Dw 3Rw2 Rw Uw F' Uw Dw' L' Uw R2 D2 3Fw2 Fw2 B' L' 3Uw Fw' Dw' L Dw' F Bw'",
+    "Lw' Fw' Bw 3Uw' L' B U' Lw' U2 L2 U2 R U2 L F' Bw 3Rw Lw Dw' Uw' B F' 3Rw' Bw' Rw2 Dw' R Fw L' 3Fw2 R 3Rw2 L2 Dw2 3Rw' R2 3Uw2 B' U2 Lw 3Fw' Rw2 Dw U' L' 3Fw2 L U2 L2 3Uw2 Rw 3Rw2 3Fw L Uw2 3Fw2 Dw Uw2 Rw 3Rw2 3Fw2 R' Bw Lw2 Uw2 Dw Lw2 3Rw' 3Uw Bw2 3Rw2 Fw' U2 Lw2 3Rw2 Rw2 Bw2 U' D R",
+    "F2 U F' Rw' B2 3Fw2 L' 3Rw' B' Rw F' Fw 3Rw2 3Uw2 R Uw2 Rw2 3Fw2 Bw2 F' U Fw2 L2 U2 Uw Lw Fw2 U' 3Rw Uw2 3Uw2 B Dw D' F2 L' D' L R B2 Dw2 F2 R Fw' R' F2 3Rw' Dw Uw2 Lw Uw2 D' F2 B 3Fw2 Rw2 U2 D L' Fw 3Fw' D' 3Rw' D' Uw U' B Dw' B Fw2 3Rw' 3Uw2 R' U' R2 3Uw D2 Uw 3Fw2 Fw",
+];
+
+/// Scrambles of the 7x7, see `cube::tests::seven_by_seven`.
+pub(crate) const SEVEN_BY_SEVEN: &[&str] = &[
+    "D 3Fw Bw2 U' Bw R2 3Bw L' 3Uw R Rw2 3Lw' L' Uw' 3Lw2 3Dw2 B2 L Rw' 3Dw B' U2 F' B U 3Rw' R F' Dw' 3Rw' 3Fw Rw2 R2 3Lw' 3Uw 3Rw2 F 3Dw Rw2 Lw' Fw' 3Bw2 U' L' 3Bw2 U2 Uw 3Bw2 Lw' U 3Lw' B Fw2 D2 3Lw' 3Bw Uw2 B D Lw 3Rw2 3Lw' Bw' Fw' 3Fw' 3Lw Dw' 3Uw Rw2 U2 B' Bw Rw' Fw' R2 F 3Rw B2 3Uw Dw' F' 3Lw2 D2 3Lw' 3Uw' Uw2 Rw 3Fw' D' Dw2 U' Fw Bw' 3Uw' Lw2 3Dw' Uw' R Rw 3Rw2",
+    "D2 Fw2 U2 3Rw 3Dw D' 3Uw' 3Bw Bw' Rw2 D U 3Uw' 3Lw F U 3Lw2 3Dw' 3Bw' 3Dw2 Fw2 3Dw' Bw2 R' Dw' R' 3Rw2 3Bw' Fw2 R2 3Dw2 Bw2 3Rw2 U 3Uw Fw' Uw2 3Uw' 3Dw Fw' Dw2 B Rw' 3Dw' B U' 3Dw2 Fw' D' 3Fw U 3Rw2 D 3Uw Dw 3Dw L2 3Lw Rw 3Rw' Dw' U Rw' L Fw2 3Fw' L' D' R2 U' Bw D' Rw2 L 3Dw' 3Fw' U Lw L Bw Uw2 L' B2 Fw' R' F' 3Dw2 3Lw' U' 3Rw2 F 3Uw' Lw 3Rw2 3Lw D' B2 3Fw D Bw2",
+    "3Bw2 Rw F Lw' 3Lw' 3Bw 3Dw Uw Bw Fw Rw 3Bw2 F' 3Lw Dw2 3Rw F Rw2 D Fw' U F' Fw 3Uw' Lw' L2 D2 Fw' D' Bw2 Fw Uw U Lw R' 3Rw' Rw' 3Fw' 3Rw' Lw' Dw' L' 3Uw D2 B2 3Rw' F2 D' Lw F2 D Dw2 Uw L2 F' 3Fw' Uw' Rw' F2 Dw2 3Rw 3Dw' R B2 U2 3Rw2 Lw' Uw' 3Bw Fw2 B2 Bw 3Rw Uw' D2 R2 3Lw2 3Dw 3Rw F' Lw Dw Bw 3Bw Dw 3Lw L' B 3Lw' B' 3Dw' Bw2 L' Rw2 D' 3Uw2 Lw B' F Bw2",
+    "3Rw B' Uw' Dw F2 Rw' Fw2 B' Dw 3Rw' U' 3Lw2 3Dw2 3Uw2 Uw' Rw2 D L' U2 Lw2 3Dw Fw2 3Rw' Rw2 3Fw 3Dw2 3Rw' Dw2 U' 3Lw' F D Rw 3Dw' 3Fw 3Dw Bw' Lw F' Dw F2 L Uw2 Rw2 Lw Fw' F B' U Dw 3Dw2 D' 3Rw' Dw2 Lw' Rw 3Bw Dw' Rw B U' Rw2 B 3Rw' Bw2 Rw2 L B2 U' R' Fw Dw' D 3Dw' Rw' Dw D Rw2 Fw' 3Bw 3Fw2 Rw 3Uw' U' 3Rw 3Uw F' 3Lw' U2 3Rw' F2 R 3Rw Dw F B' Dw2 F2 Rw2 3Rw'",
+    "3Lw2 Fw 3Rw2 Dw' Uw R' Dw2 Bw2 Uw Dw Lw2 Fw' L Bw 3Rw 3Uw2 Uw' F' U 3Uw' 3Rw2 3Dw' 3Bw2 Dw L Lw D Fw' 3Uw2 Fw' Dw' 3Dw2 Rw2 F' Lw Rw Fw' 3Rw' 3Fw' Rw2 B' 3Uw 3Dw' Bw2 Fw2 U2 F L' U2 B' Bw Fw2 3Uw D' F' 3Lw' R2 3Bw2 Fw' 3Rw' Dw' U' 3Dw2 Rw2 3Dw' 3Uw 3Rw' Fw2 3Uw' L 3Rw' Bw 3Bw Dw2 Fw R2 D2 3Rw' 3Dw B Fw Uw F2 Uw2 3Bw' Dw' 3Dw' 3Bw R2 3Bw 3Rw' Uw2 R2 3Uw2 U2 3Dw2 3Lw 3Rw' U2 Rw",
+];
+
+/// Scrambles of the megaminx, see `megaminx::tests::megaminx`.
+pub(crate) const MEGAMINX: &[&str] = &[
+    "R-- D++ R++ D-- R-- D++ R-- D-- R++ D-- U' \
+     R++ D-- R++ D++ R-- D++ R++ D-- R++ D++ U \
+     R-- D-- R-- D++ R-- D++ R++ D++ R++ D-- U' \
+     R++ D-- R++ D++ R++ D++ R++ D++ R++ D++ U \
+     R-- D-- R++ D-- R-- D++ R-- D-- R-- D-- U' \
+     R-- D-- R-- D-- R++ D-- R-- D++ R++ D++ U \
+     R++ D-- R-- D++ R++ D-- R++ D-- R++ D++ U",
+    "R++ D-- R++ D-- R-- D++ R++ D-- R-- D-- U' \
+     R++ D-- R-- D-- R++ D++ R-- D-- R++ D++ U \
+     R++ D-- R-- D-- R++ D-- R-- D-- R-- D++ U \
+     R-- D++ R-- D-- R++ D++ R++ D-- R++ D++ U \
+     R++ D-- R-- D-- R-- D++ R-- D-- R-- D-- U' \
+     R++ D++ R++ D-- R++ D-- R-- D++ R-- D-- U' \
+     R++ D++ R-- D-- R-- D++ R-- D-- R-- D-- U'",
+    "R-- D++ R++ D++ R++ D++ R-- D++ R++ D-- U' \
+     R++ D-- R-- D++ R++ D-- R++ D++ R++ D-- U' \
+     R++ D-- R-- D-- R++ D++ R-- D-- R-- D++ U \
+     R-- D-- R++ D-- R++ D++ R++ D-- R-- D-- U' \
+     R++ D-- R++ D++ R++ D++ R++ D-- R++ D-- U' \
+     R++ D-- R-- D++ R++ D-- R-- D++ R++ D-- U' \
+     R-- D++ R++ D++ R-- D++ R-- D-- R-- D++ U",
+    "R-- D++ R-- D-- R++ D-- R-- D++ R-- D-- U' \
+     R-- D-- R++ D++ R-- D-- R-- D-- R++ D++ U \
+     R++ D++ R-- D-- R-- D-- R++ D++ R-- D-- U' \
+     R-- D-- R++ D++ R++ D++ R++ D++ R-- D-- U' \
+     R++ D++ R++ D++ R++ D++ R-- D++ R-- D-- U' \
+     R++ D-- R++ D++ R++ D-- R-- D-- R-- D++ U \
+     R++ D-- R-- D++ R-- D++ R-- D-- R-- D++ U",
+    "R++ D-- R++ D-- R-- D-- R-- D-- R++ D++ U \
+     R-- D++ R-- D++ R++ D++ R++ D++ R++ D++ U \
+     R-- D-- R-- D++ R-- D++ R++ D++ R++ D-- U' \
+     R-- D-- R-- D-- R++ D-- R++ D-- R-- D-- U' \
+     R++ D-- R++ D-- R-- D-- R-- D-- R-- D++ U \
+     R++ D++ R++ D++ R++ D-- R-- D++ R-- D-- U' \
+     R-- D-- R++ D-- R-- D-- R++ D++ R++ D++ U",
+];
+
+/// Scrambles of the 2x2x3, see `cuboid::tests::two_by_two_by_three`.
+pub(crate) const TWO_BY_TWO_BY_THREE: &[&str] = &["U R2 U' 2D F2 Uw2 B2"];
+
+/// Scrambles of the 3x3x2, see `cuboid::tests::three_by_three_by_two`.
+pub(crate) const THREE_BY_THREE_BY_TWO: &[&str] = &["R2 U F2 D' L2 B2 U2 R2"];
+
+/// Scrambles of the 3x3x4, see `cuboid::tests::three_by_three_by_four`.
+pub(crate) const THREE_BY_THREE_BY_FOUR: &[&str] =
+    &["U 2D' R2 F2 Uw' B2 L2 U2 3Uw F2 D' R2 B2 2U2 L2 D 2R2 Fw2 U' 3D B2 Rw2 D2"];
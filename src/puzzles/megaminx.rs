@@ -1,25 +1,76 @@
-use crate::puzzles::{ColorSchemes, Puzzle};
+use crate::puzzles::cube::permutation_parity;
+use crate::puzzles::metrics::MoveCount;
+use crate::puzzles::render::{
+    document, escape, outline, palette, pattern_id, patterns, Accessibility, Stickers,
+};
+use crate::puzzles::{state_json, ColorSchemes, Puzzle, RenderOptions, ScrambleError, StateError};
 use crate::utils::color::Color;
 use crate::utils::maths::get_line_intersection;
 use lazy_static::lazy_static;
-use ndarray::{s, Array2, ArrayView1};
+use ndarray::{s, Array2};
 use std::collections::HashMap;
 use std::f64::consts::PI;
 use std::fmt;
-use svg::node::element::path::Position::Absolute;
-use svg::node::element::path::{Command, Data, Parameters};
-use svg::node::element::{Group, Path, SVG};
-use svg::{Document, Node};
+use svg::node::element::path::{Command, Data};
+use svg::node::element::{Group, Path, Text, SVG};
+use svg::Node;
+
+/// Every move of Pochmann notation, used for megaminx scrambles.
+const NOTATION: [&str; 6] = ["R++", "R--", "D++", "D--", "U", "U'"];
+
+/// Reads every move of a scramble in Pochmann notation.
+pub fn parse_scramble(scramble: &str) -> Result<Vec<&str>, ScrambleError> {
+    let moves: Vec<&str> = scramble.split_ascii_whitespace().collect();
+    if let Some(index) = moves.iter().position(|mv| !NOTATION.contains(mv)) {
+        return Err(ScrambleError::Notation {
+            index,
+            found: moves[index].to_string(),
+        });
+    }
+
+    Ok(moves)
+}
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MegaminxData"))]
 pub struct Megaminx {
     pub state: Array2<Face>,
     color_scheme: HashMap<Face, Color>,
+    lettering: Option<LetteringScheme>,
+    letter_centers: bool,
     minx_rad: f64,
     gap: f64,
 }
 
+/// Labels for the ten stickers around the center of each face, in the order of
+/// `Megaminx::state`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LetteringScheme {
+    labels: Vec<String>,
+}
+
+impl Default for LetteringScheme {
+    /// The facelet letter of the face followed by the index of the sticker, `A0` to `L9`.
+    fn default() -> Self {
+        LetteringScheme {
+            labels: (0..12)
+                .flat_map(|f| (0..10).map(move |i| format!("{}{i}", Face::from(f).facelet())))
+                .collect(),
+        }
+    }
+}
+
+impl LetteringScheme {
+    /// A scheme from 120 labels, ten per face, returns `None` for any other number.
+    pub fn new(labels: Vec<String>) -> Option<Self> {
+        (labels.len() == 120).then_some(LetteringScheme { labels })
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Face {
     U,
     Bl,
@@ -55,6 +106,21 @@ impl From<usize> for Face {
     }
 }
 
+impl Face {
+    /// Faces are lettered `A` to `L` in facelet strings, in the order they are declared.
+    fn facelet(self) -> char {
+        (b'A' + self as u8) as char
+    }
+
+    fn from_facelet(c: char) -> Option<Face> {
+        if ('A'..='L').contains(&c) {
+            Some(Face::from(c as usize - 'A' as usize))
+        } else {
+            None
+        }
+    }
+}
+
 impl fmt::Display for Face {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{self:?}")
@@ -80,6 +146,53 @@ lazy_static! {
     pub static ref UNFOLDWIDTH: f64 = 4. * (0.1 * PI).cos() + 2. * (0.3 * PI).cos();
 }
 
+/// A megaminx as it is deserialized, before checking that its state is one of a megaminx.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MegaminxData {
+    state: Array2<Face>,
+    color_scheme: HashMap<Face, Color>,
+    lettering: Option<LetteringScheme>,
+    letter_centers: bool,
+    minx_rad: f64,
+    gap: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MegaminxData> for Megaminx {
+    type Error = StateError;
+
+    fn try_from(data: MegaminxData) -> Result<Self, Self::Error> {
+        let mega = Megaminx::new();
+        if data.state.dim() != mega.state.dim() {
+            let (faces, stickers) = mega.state.dim();
+            let found = data.state.dim();
+            return Err(StateError::Shape {
+                expected: (faces, stickers, 1),
+                found: (found.0, found.1, 1),
+            });
+        }
+        check_stickers(&data.state)?;
+        if let Some(lettering) = &data.lettering {
+            if lettering.labels.len() != 120 {
+                return Err(StateError::Lettering {
+                    expected: 120,
+                    found: lettering.labels.len(),
+                });
+            }
+        }
+
+        Ok(Megaminx {
+            state: data.state,
+            color_scheme: data.color_scheme,
+            lettering: data.lettering,
+            letter_centers: data.letter_centers,
+            minx_rad: data.minx_rad,
+            gap: data.gap,
+        })
+    }
+}
+
 impl Puzzle for Megaminx {
     fn new() -> Self {
         Megaminx {
@@ -87,37 +200,97 @@ impl Puzzle for Megaminx {
             minx_rad: 30.,
             state: Array2::<Face>::from_shape_fn((12, 11), |(i, _)| Face::from(i)),
             color_scheme: DEFAULT_COLOR_SCHEME.clone(),
+            lettering: None,
+            letter_centers: false,
         }
     }
 
-    fn apply_scramble(&mut self, scramble: &str) {
-        if scramble.trim().is_empty() {
-            return;
-        }
+    fn try_apply_scramble(&mut self, scramble: &str) -> Result<(), ScrambleError> {
+        let moves = parse_scramble(scramble)?;
 
-        let moves = scramble.split_ascii_whitespace();
-        moves.for_each(|mv| self.apply_move(mv));
+        moves.iter().for_each(|mv| self.apply_move(mv));
+        Ok(())
+    }
+
+    fn move_count(&self, alg: &str) -> Result<MoveCount, ScrambleError> {
+        MoveCount::megaminx(alg)
     }
 
     fn get_default_color_scheme(&self) -> ColorSchemes {
         ColorSchemes::Megaminx(DEFAULT_COLOR_SCHEME.clone())
     }
 
-    fn draw(&self) -> SVG {
-        let (width, height) = self.get_preferred_size();
+    fn to_facelets(&self) -> String {
+        self.state.iter().map(|f| f.facelet()).collect()
+    }
+
+    fn to_json(&self) -> String {
+        let faces: Vec<(String, Vec<String>)> = self
+            .state
+            .outer_iter()
+            .enumerate()
+            .map(|(i, stickers)| {
+                (
+                    Face::from(i).to_string(),
+                    stickers.iter().map(Face::to_string).collect(),
+                )
+            })
+            .collect();
+
+        state_json("megaminx", None, &faces)
+    }
 
-        let mut svg = Document::new()
-            .set("viewBox", (0, 0, width, height))
-            .set("height", "100%")
-            .set("width", "100%");
+    fn draw_with(&self, options: &RenderOptions) -> SVG {
+        let (width, height) = self.get_preferred_size(options);
 
-        self.draw_minx(&mut svg);
+        let mut svg = document(width, height, options, "Megaminx", || self.describe());
+        self.draw_minx(&mut svg, options);
         svg
     }
 }
 
 impl Megaminx {
-    fn pentagon(&self, xt: f64, yt: f64, pointup: bool) -> Data {
+    /// Builds a megaminx from a 132 character facelet string: the eleven stickers of each face
+    /// as stored in `Megaminx::state`, faces lettered `A` to `L` in the order of [`Face`].
+    ///
+    /// The state must be reachable, see [`StateError`] for what is checked.
+    pub fn from_facelets(facelets: &str) -> Result<Self, StateError> {
+        let mut mega = Megaminx::new();
+        let (faces, stickers) = mega.state.dim();
+
+        let found = facelets.chars().count();
+        if found != faces * stickers {
+            return Err(StateError::Length {
+                expected: faces * stickers,
+                found,
+            });
+        }
+
+        for (index, c) in facelets.chars().enumerate() {
+            let face = Face::from_facelet(c).ok_or(StateError::Facelet { index, found: c })?;
+            mega.state[[index / stickers, index % stickers]] = face;
+        }
+
+        check_stickers(&mega.state)?;
+        Ok(mega)
+    }
+
+    /// Draws the label of every sticker from `scheme` on the place it is on. Centers are only
+    /// labelled with `centers`, with the name of their face.
+    pub fn set_lettering(&mut self, scheme: Option<LetteringScheme>, centers: bool) {
+        self.lettering = scheme;
+        self.letter_centers = centers;
+    }
+
+    /// Face radius and gap between faces, from the options or the megaminx's own.
+    fn layout(&self, options: &RenderOptions) -> (f64, f64) {
+        (
+            options.sticker_size.unwrap_or(self.minx_rad),
+            options.gap.unwrap_or(self.gap),
+        )
+    }
+
+    fn pentagon(minx_rad: f64, xt: f64, yt: f64, pointup: bool) -> Data {
         const LEN: usize = 5;
 
         let mut angs = [1.3, 1.7, 0.1, 0.5, 0.9];
@@ -133,8 +306,8 @@ impl Megaminx {
         let mut y = [0.; LEN];
 
         for i in 0..LEN {
-            x[i] = self.minx_rad * angs[i].cos();
-            y[i] = self.minx_rad * angs[i].sin();
+            x[i] = minx_rad * angs[i].cos();
+            y[i] = minx_rad * angs[i].sin();
         }
 
         Data::new()
@@ -146,90 +319,125 @@ impl Megaminx {
             .close()
     }
 
-    fn get_face_boundaries(&self) -> HashMap<Face, Data> {
-        let x = self.minx_rad * (2. * (1. - (0.6 * PI).cos())).sqrt();
-        let a = self.minx_rad * (0.1 * PI).cos();
+    fn get_face_boundaries(&self, options: &RenderOptions) -> HashMap<Face, Data> {
+        let (minx_rad, gap) = self.layout(options);
+        let x = minx_rad * (2. * (1. - (0.6 * PI).cos())).sqrt();
+        let a = minx_rad * (0.1 * PI).cos();
         let b = x * (0.1 * PI).cos();
         let c = x * (0.3 * PI).cos();
         let d = x * (0.1 * PI).sin();
         let e = x * (0.3 * PI).sin();
 
-        let left_center_x = self.gap + a + b + d / 2.;
-        let left_center_y = self.gap + x + self.minx_rad - d;
+        let left_center_x = gap + a + b + d / 2.;
+        let left_center_y = gap + x + minx_rad - d;
 
         let f = (0.1 * PI).cos();
         let gg = (0.2 * PI).cos();
 
-        let magic_shift_number = d * 0.6 + self.minx_rad * (f + gg);
+        let magic_shift_number = d * 0.6 + minx_rad * (f + gg);
         let shift = left_center_x + magic_shift_number;
 
         let mut faces = HashMap::new();
-        faces.insert(Face::U, self.pentagon(left_center_x, left_center_y, true));
+        faces.insert(
+            Face::U,
+            Megaminx::pentagon(minx_rad, left_center_x, left_center_y, true),
+        );
         faces.insert(
             Face::Bl,
-            self.pentagon(left_center_x - c, left_center_y - e, false),
+            Megaminx::pentagon(minx_rad, left_center_x - c, left_center_y - e, false),
         );
         faces.insert(
             Face::Br,
-            self.pentagon(left_center_x + c, left_center_y - e, false),
+            Megaminx::pentagon(minx_rad, left_center_x + c, left_center_y - e, false),
         );
         faces.insert(
             Face::R,
-            self.pentagon(left_center_x + b, left_center_y + d, false),
+            Megaminx::pentagon(minx_rad, left_center_x + b, left_center_y + d, false),
         );
         faces.insert(
             Face::F,
-            self.pentagon(left_center_x, left_center_y + x, false),
+            Megaminx::pentagon(minx_rad, left_center_x, left_center_y + x, false),
         );
         faces.insert(
             Face::L,
-            self.pentagon(left_center_x - b, left_center_y + d, false),
+            Megaminx::pentagon(minx_rad, left_center_x - b, left_center_y + d, false),
         );
         faces.insert(
             Face::D,
-            self.pentagon(
-                shift + self.gap + a + b,
-                self.gap + x + self.minx_rad,
-                false,
-            ),
+            Megaminx::pentagon(minx_rad, shift + gap + a + b, gap + x + minx_rad, false),
         );
         faces.insert(
             Face::Dr,
-            self.pentagon(
-                shift + self.gap + a + b - c,
-                self.gap + x + e + self.minx_rad,
+            Megaminx::pentagon(
+                minx_rad,
+                shift + gap + a + b - c,
+                gap + x + e + minx_rad,
                 true,
             ),
         );
         faces.insert(
             Face::Dbr,
-            self.pentagon(shift + self.gap + a, self.gap + x - d + self.minx_rad, true),
+            Megaminx::pentagon(minx_rad, shift + gap + a, gap + x - d + minx_rad, true),
         );
         faces.insert(
             Face::B,
-            self.pentagon(shift + self.gap + a + b, self.gap + self.minx_rad, true),
+            Megaminx::pentagon(minx_rad, shift + gap + a + b, gap + minx_rad, true),
         );
         faces.insert(
             Face::Dbl,
-            self.pentagon(
-                shift + self.gap + a + 2. * b,
-                self.gap + x - d + self.minx_rad,
+            Megaminx::pentagon(
+                minx_rad,
+                shift + gap + a + 2. * b,
+                gap + x - d + minx_rad,
                 true,
             ),
         );
         faces.insert(
             Face::Dl,
-            self.pentagon(
-                shift + self.gap + a + b + c,
-                self.gap + x + e + self.minx_rad,
+            Megaminx::pentagon(
+                minx_rad,
+                shift + gap + a + b + c,
+                gap + x + e + minx_rad,
                 true,
             ),
         );
         faces
     }
 
-    fn draw_minx(&self, svg: &mut SVG) {
-        let pentagons = self.get_face_boundaries();
+    /// The stickers of each face in state order with the center last, named by the face they
+    /// belong on, for screen readers.
+    fn describe(&self) -> String {
+        let faces: Vec<String> = self
+            .state
+            .outer_iter()
+            .enumerate()
+            .map(|(i, stickers)| {
+                let stickers: Vec<String> = stickers.iter().map(Face::to_string).collect();
+                format!("{} face: {}.", Face::from(i), stickers.join(" "))
+            })
+            .collect();
+        faces.join(" ")
+    }
+
+    fn draw_minx(&self, svg: &mut SVG, options: &RenderOptions) {
+        let pentagons = self.get_face_boundaries(options);
+
+        let (minx_rad, _) = self.layout(options);
+        let color_scheme = options
+            .megaminx_colors
+            .as_ref()
+            .unwrap_or(&self.color_scheme);
+        let face_colors = (0..12).map(|i| {
+            let face = Face::from(i);
+            let color = color_scheme.get(&face).copied().unwrap_or(Color::black());
+            (i, face, color)
+        });
+        if let Some(defs) = patterns("megaminx", face_colors.clone(), minx_rad / 8., options) {
+            svg.append(defs);
+        }
+        if let Some(style) = palette(face_colors.map(|(_, _, color)| color), options) {
+            svg.append(style);
+        }
 
         let mut g = Group::new().set("transform", "translate(0.5,0.5)");
 
@@ -243,34 +451,49 @@ impl Megaminx {
                 _ => panic!(),
             };
 
-            let label = if face == &Face::U || face == &Face::F {
+            let label = if self.letter_centers
+                || self.lettering.is_none() && (face == &Face::U || face == &Face::F)
+            {
                 Some(face.to_string())
             } else {
                 None
             };
 
-            Megaminx::draw_pentagon(
+            self.draw_pentagon(
                 &mut g,
                 pentagons.get(face).unwrap(),
-                &self.state.slice(s![f, ..]),
+                *face,
                 rotate_counter_clockwise,
                 label,
-                &self.color_scheme,
+                options,
             );
         }
         svg.append(g);
     }
 
     fn draw_pentagon(
+        &self,
         g: &mut Group,
         p: &Data,
-        state: &ArrayView1<Face>,
+        face: Face,
         rotate_counter_clockwise: usize,
         label: Option<String>,
-        color_scheme: &HashMap<Face, Color>,
+        options: &RenderOptions,
     ) {
         const LEN: usize = 5;
 
+        let f = face as usize;
+        let state = self.state.slice(s![f, ..]);
+        let letters = self
+            .lettering
+            .as_ref()
+            .map(|scheme| &scheme.labels[10 * f..10 * f + 10]);
+        let (minx_rad, _) = self.layout(options);
+        let color_scheme = options
+            .megaminx_colors
+            .as_ref()
+            .unwrap_or(&self.color_scheme);
+
         let mut xpoints = [0_f64; LEN];
         let mut ypoints = [0_f64; LEN];
 
@@ -294,83 +517,86 @@ impl Megaminx {
             ys[i + 5] = 0.6 * ypoints[(i + 1) % 5] + 0.4 * ypoints[i];
         }
 
-        let mut ps = Vec::with_capacity(11);
-
-        for _ in 0..11 {
-            ps.push(Data::new())
-        }
-
         let mut intpent = [(0., 0.); LEN];
 
         for i in 0..LEN {
             intpent[i] = get_line_intersection(
-                xs[i],
-                ys[i],
-                xs[5 + (3 + i) % 5],
-                ys[5 + (3 + i) % 5],
-                xs[(i + 1) % 5],
-                ys[(i + 1) % 5],
-                xs[5 + (4 + i) % 5],
-                ys[5 + (4 + i) % 5],
+                (xs[i], ys[i]),
+                (xs[5 + (3 + i) % 5], ys[5 + (3 + i) % 5]),
+                (xs[(i + 1) % 5], ys[(i + 1) % 5]),
+                (xs[5 + (4 + i) % 5], ys[5 + (4 + i) % 5]),
             );
-            if i == 0 {
-                ps[10].append(Command::Move(
-                    Absolute,
-                    Parameters::from((intpent[i].0, intpent[i].1)),
-                ));
-            } else {
-                ps[10].append(Command::Line(
-                    Absolute,
-                    Parameters::from((intpent[i].0, intpent[i].1)),
-                ));
-            }
         }
-        ps[10].append(Command::Close);
 
+        // Corners at even indices, edges at odd ones and the center last.
+        let mut polygons = Vec::with_capacity(11);
         for i in 0..LEN {
-            ps[2 * i].append(Command::Move(
-                Absolute,
-                Parameters::from((xpoints[i], ypoints[i])),
-            ));
-            ps[2 * i].append(Command::Line(Absolute, Parameters::from((xs[i], ys[i]))));
-            ps[2 * i].append(Command::Line(
-                Absolute,
-                Parameters::from((intpent[i].0, intpent[i].1)),
-            ));
-            ps[2 * i].append(Command::Line(
-                Absolute,
-                Parameters::from((xs[5 + (4 + i) % 5], ys[5 + (4 + i) % 5])),
-            ));
-            ps[2 * i].append(Command::Close);
-
-            ps[2 * i + 1].append(Command::Move(Absolute, Parameters::from((xs[i], ys[i]))));
-            ps[2 * i + 1].append(Command::Line(
-                Absolute,
-                Parameters::from((xs[i + 5], ys[i + 5])),
-            ));
-            ps[2 * i + 1].append(Command::Line(
-                Absolute,
-                Parameters::from((intpent[(i + 1) % 5].0, intpent[(i + 1) % 5].1)),
-            ));
-            ps[2 * i + 1].append(Command::Line(
-                Absolute,
-                Parameters::from((intpent[i].0, intpent[i].1)),
-            ));
-            ps[2 * i + 1].append(Command::Close);
-        }
-
-        for (i, p) in ps.iter().enumerate() {
+            polygons.push(vec![
+                (xpoints[i], ypoints[i]),
+                (xs[i], ys[i]),
+                intpent[i],
+                (xs[5 + (4 + i) % 5], ys[5 + (4 + i) % 5]),
+            ]);
+            polygons.push(vec![
+                (xs[i], ys[i]),
+                (xs[i + 5], ys[i + 5]),
+                intpent[(i + 1) % 5],
+                intpent[i],
+            ]);
+        }
+        polygons.push(intpent.to_vec());
+
+        if options.padding > 0. {
+            let corners: Vec<(f64, f64)> = xpoints.into_iter().zip(ypoints).collect();
+            g.append(
+                Path::new()
+                    .set("fill", options.stroke.to_string())
+                    .set("d", outline(&corners, options)),
+            );
+        }
+
+        let letter = |text: String, (x, y): (f64, f64), color: &Color| {
+            Text::new()
+                .set("x", options.round(x))
+                .set("y", options.round(y))
+                .set("text-anchor", "middle")
+                .set("dominant-baseline", "central")
+                .set("font-family", "sans-serif")
+                .set("font-size", minx_rad / 6.)
+                .set("fill", color.contrasting().to_string())
+                .add(svg::node::Text::new(escape(&text)))
+        };
+
+        let mut stickers = Stickers::new(g, options);
+        for (i, polygon) in polygons.iter().enumerate() {
             let j = if i < 10 {
                 (i + 2 * rotate_counter_clockwise) % 10
             } else {
                 i
             };
-            let path = Path::new()
-                .set("fill", color_scheme.get(&state[j]).unwrap().to_string())
-                .set("stroke", "black")
-                .set("d", p.clone());
-            g.append(path);
+            let color = color_scheme.get(&state[j]).unwrap();
+            let center = centroid(polygon);
+
+            let sticker = shrink(polygon, center, options.padding);
+            stickers.polygon(g, &sticker, *color);
+            if options.accessibility == Some(Accessibility::Patterns) {
+                let fill = format!("url(#{})", pattern_id("megaminx", state[j]));
+                g.append(
+                    Path::new()
+                        .set("fill", fill)
+                        .set("d", outline(&sticker, options)),
+                );
+            }
+
+            if let (Some(letters), true) = (letters, i < 10) {
+                g.append(letter(letters[j].clone(), center, color));
+            } else if options.accessibility == Some(Accessibility::Glyphs)
+                && (i < 10 || label.is_none())
+            {
+                g.append(letter(state[j].to_string(), center, color));
+            }
         }
+        stickers.finish(g);
 
         if let Some(label) = label {
             let mut center_x = 0.;
@@ -383,12 +609,20 @@ impl Megaminx {
             center_x /= intpent.len() as f64;
             center_y /= intpent.len() as f64;
 
-            let mut label_text = svg::node::element::Text::new();
+            let mut label_text = Text::new();
 
-            label_text.assign("x", center_x);
-            label_text.assign("y", center_y);
+            label_text.assign("x", options.round(center_x));
+            label_text.assign("y", options.round(center_y));
             label_text.assign("text-anchor", "middle");
             label_text.assign("dy", "0.7ex");
+            label_text.assign(
+                "fill",
+                color_scheme
+                    .get(&state[10])
+                    .unwrap()
+                    .contrasting()
+                    .to_string(),
+            );
             label_text.append(svg::node::Text::new(label));
 
             //label_text.setStyle("dominant-baseline", "central");
@@ -396,10 +630,11 @@ impl Megaminx {
         }
     }
 
-    fn get_preferred_size(&self) -> (f64, f64) {
+    fn get_preferred_size(&self, options: &RenderOptions) -> (f64, f64) {
+        let (minx_rad, gap) = self.layout(options);
         (
-            *UNFOLDWIDTH * 2. * self.minx_rad + 3. * self.gap,
-            *UNFOLDHEIGHT * self.minx_rad + 2. * self.gap,
+            *UNFOLDWIDTH * 2. * minx_rad + 3. * gap,
+            *UNFOLDHEIGHT * minx_rad + 2. * gap,
         )
     }
     fn apply_move(&mut self, mv: &str) {
@@ -410,7 +645,7 @@ impl Megaminx {
             "D--" => self.big_turn(Face::D, 3),
             "U" => self.turn(Face::U, 1),
             "U'" => self.turn(Face::U, 4),
-            _ => panic!(),
+            _ => unreachable!("{mv} is not in Pochmann notation"),
         }
     }
 
@@ -426,39 +661,23 @@ impl Megaminx {
         let b = if s >= 6 { 6 } else { 0 };
 
         match s % 6 {
-            0 => self.swap_on_side(b, 1, 6, 5, 4, 4, 2, 3, 0, 2, 8),
-            1 => self.swap_on_side(b, 0, 0, 2, 0, 9, 6, 10, 6, 5, 2),
-            2 => self.swap_on_side(b, 0, 2, 3, 2, 8, 4, 9, 4, 1, 4),
-            3 => self.swap_on_side(b, 0, 4, 4, 4, 7, 2, 8, 2, 2, 6),
-            4 => self.swap_on_side(b, 0, 6, 5, 6, 11, 0, 7, 0, 3, 8),
-            5 => self.swap_on_side(b, 0, 8, 1, 8, 10, 8, 11, 8, 4, 0),
+            0 => self.swap_on_side(b, [(1, 6), (5, 4), (4, 2), (3, 0), (2, 8)]),
+            1 => self.swap_on_side(b, [(0, 0), (2, 0), (9, 6), (10, 6), (5, 2)]),
+            2 => self.swap_on_side(b, [(0, 2), (3, 2), (8, 4), (9, 4), (1, 4)]),
+            3 => self.swap_on_side(b, [(0, 4), (4, 4), (7, 2), (8, 2), (2, 6)]),
+            4 => self.swap_on_side(b, [(0, 6), (5, 6), (11, 0), (7, 0), (3, 8)]),
+            5 => self.swap_on_side(b, [(0, 8), (1, 8), (10, 8), (11, 8), (4, 0)]),
             _ => panic!(),
         }
 
         self.rotate_face(f);
     }
 
-    fn swap_on_side(
-        &mut self,
-        b: usize,
-        f1: usize,
-        s1: usize,
-        f2: usize,
-        s2: usize,
-        f3: usize,
-        s3: usize,
-        f4: usize,
-        s4: usize,
-        f5: usize,
-        s5: usize,
-    ) {
+    /// Cycles three stickers from each of five faces offset by `b`, starting at the given
+    /// sticker of each.
+    fn swap_on_side(&mut self, b: usize, cycle: [(usize, usize); 5]) {
         for i in 0..3 {
-            let temp = self.state[[(f1 + b) % 12, (s1 + i) % 10]];
-            self.state[[(f1 + b) % 12, (s1 + i) % 10]] = self.state[[(f2 + b) % 12, (s2 + i) % 10]];
-            self.state[[(f2 + b) % 12, (s2 + i) % 10]] = self.state[[(f3 + b) % 12, (s3 + i) % 10]];
-            self.state[[(f3 + b) % 12, (s3 + i) % 10]] = self.state[[(f4 + b) % 12, (s4 + i) % 10]];
-            self.state[[(f4 + b) % 12, (s4 + i) % 10]] = self.state[[(f5 + b) % 12, (s5 + i) % 10]];
-            self.state[[(f5 + b) % 12, (s5 + i) % 10]] = temp;
+            self.swap(cycle.map(|(f, s)| ((f + b) % 12, (s + i) % 10)));
         }
     }
 
@@ -473,97 +692,60 @@ impl Megaminx {
     fn _big_turn(&mut self, f: Face) {
         if f == Face::Dbr {
             for i in 0..7 {
-                self.swap(
-                    0,
-                    (1 + i) % 10,
-                    4,
-                    (3 + i) % 10,
-                    11,
-                    (1 + i) % 10,
-                    10,
-                    (1 + i) % 10,
-                    1,
-                    (1 + i) % 10,
-                );
+                self.swap([
+                    (0, (1 + i) % 10),
+                    (4, (3 + i) % 10),
+                    (11, (1 + i) % 10),
+                    (10, (1 + i) % 10),
+                    (1, (1 + i) % 10),
+                ]);
             }
 
             self.swap_centers(0, 4, 11, 10, 1);
 
-            self.swap_whole_face(2, 0, 3, 0, 7, 0, 6, 8, 9, 8);
+            self.swap_whole_face([(2, 0), (3, 0), (7, 0), (6, 8), (9, 8)]);
 
             self.rotate_face(Face::Dbr);
         } else {
             assert_eq!(f, Face::D);
             for i in 0..7 {
-                self.swap(
-                    1,
-                    (9 + i) % 10,
-                    2,
-                    (1 + i) % 10,
-                    3,
-                    (3 + i) % 10,
-                    4,
-                    (5 + i) % 10,
-                    5,
-                    (7 + i) % 10,
-                );
+                self.swap([
+                    (1, (9 + i) % 10),
+                    (2, (1 + i) % 10),
+                    (3, (3 + i) % 10),
+                    (4, (5 + i) % 10),
+                    (5, (7 + i) % 10),
+                ]);
             }
 
             self.swap_centers(1, 2, 3, 4, 5);
 
-            self.swap_whole_face(11, 0, 10, 8, 9, 6, 8, 4, 7, 2);
+            self.swap_whole_face([(11, 0), (10, 8), (9, 6), (8, 4), (7, 2)]);
 
             self.rotate_face(Face::D);
         }
     }
 
-    fn swap(
-        &mut self,
-        f1: usize,
-        s1: usize,
-        f2: usize,
-        s2: usize,
-        f3: usize,
-        s3: usize,
-        f4: usize,
-        s4: usize,
-        f5: usize,
-        s5: usize,
-    ) {
-        let temp: Face = self.state[[f1, s1]];
-        self.state[[f1, s1]] = self.state[[f2, s2]];
-        self.state[[f2, s2]] = self.state[[f3, s3]];
-        self.state[[f3, s3]] = self.state[[f4, s4]];
-        self.state[[f4, s4]] = self.state[[f5, s5]];
-        self.state[[f5, s5]] = temp;
+    /// Cycles five stickers, given as face and sticker index.
+    fn swap(&mut self, cycle: [(usize, usize); 5]) {
+        let temp: Face = self.state[cycle[0]];
+        for i in 0..4 {
+            self.state[cycle[i]] = self.state[cycle[i + 1]];
+        }
+        self.state[cycle[4]] = temp;
     }
 
     fn swap_centers(&mut self, f1: usize, f2: usize, f3: usize, f4: usize, f5: usize) {
-        self.swap(f1, 10, f2, 10, f3, 10, f4, 10, f5, 10);
-    }
-
-    fn swap_whole_face(
-        &mut self,
-        f1: usize,
-        s1: usize,
-        f2: usize,
-        s2: usize,
-        f3: usize,
-        s3: usize,
-        f4: usize,
-        s4: usize,
-        f5: usize,
-        s5: usize,
-    ) {
+        self.swap([(f1, 10), (f2, 10), (f3, 10), (f4, 10), (f5, 10)]);
+    }
+
+    /// Cycles every sticker of five faces, starting at the given sticker of each.
+    fn swap_whole_face(&mut self, cycle: [(usize, usize); 5]) {
         for i in 0..10 {
-            let temp: Face = self.state[[(f1) % 12, (s1 + i) % 10]];
-            self.state[[(f1) % 12, (s1 + i) % 10]] = self.state[[(f2) % 12, (s2 + i) % 10]];
-            self.state[[(f2) % 12, (s2 + i) % 10]] = self.state[[(f3) % 12, (s3 + i) % 10]];
-            self.state[[(f3) % 12, (s3 + i) % 10]] = self.state[[(f4) % 12, (s4 + i) % 10]];
-            self.state[[(f4) % 12, (s4 + i) % 10]] = self.state[[(f5) % 12, (s5 + i) % 10]];
-            self.state[[(f5) % 12, (s5 + i) % 10]] = temp;
+            self.swap(cycle.map(|(f, s)| (f % 12, (s + i) % 10)));
         }
 
+        let [f1, f2, f3, f4, f5] = cycle.map(|(f, _)| f);
         self.swap_centers(f1, f2, f3, f4, f5);
     }
 
@@ -583,26 +765,380 @@ impl Megaminx {
     }
 }
 
+/// Checks that every color is on as many stickers as a face has, and once on a center.
+/// A sticker position on the megaminx: face and index in `Megaminx::state`.
+type Position = (Face, usize);
+
+/// Corner positions, named after their faces, each with its stickers going around it the same
+/// way as every other corner.
+const CORNERS: [(&str, [Position; 3]); 20] = [
+    ("U-L-Bl", [(Face::U, 0), (Face::L, 4), (Face::Bl, 8)]),
+    ("U-Bl-Br", [(Face::U, 2), (Face::Bl, 6), (Face::Br, 0)]),
+    ("U-Br-R", [(Face::U, 4), (Face::Br, 8), (Face::R, 2)]),
+    ("U-R-F", [(Face::U, 6), (Face::R, 0), (Face::F, 4)]),
+    ("U-F-L", [(Face::U, 8), (Face::F, 2), (Face::L, 6)]),
+    ("Bl-L-Dbl", [(Face::Bl, 0), (Face::L, 2), (Face::Dbl, 8)]),
+    ("Bl-Dbl-B", [(Face::Bl, 2), (Face::Dbl, 6), (Face::B, 8)]),
+    ("Bl-B-Br", [(Face::Bl, 4), (Face::B, 6), (Face::Br, 2)]),
+    ("Br-B-Dbr", [(Face::Br, 4), (Face::B, 4), (Face::Dbr, 6)]),
+    ("Br-Dbr-R", [(Face::Br, 6), (Face::Dbr, 4), (Face::R, 4)]),
+    ("R-Dbr-Dr", [(Face::R, 6), (Face::Dbr, 2), (Face::Dr, 4)]),
+    ("R-Dr-F", [(Face::R, 8), (Face::Dr, 2), (Face::F, 6)]),
+    ("F-Dl-L", [(Face::F, 0), (Face::Dl, 0), (Face::L, 8)]),
+    ("F-Dr-Dl", [(Face::F, 8), (Face::Dr, 0), (Face::Dl, 2)]),
+    ("L-Dl-Dbl", [(Face::L, 0), (Face::Dl, 8), (Face::Dbl, 0)]),
+    ("D-Dl-Dr", [(Face::D, 0), (Face::Dl, 4), (Face::Dr, 8)]),
+    ("D-Dr-Dbr", [(Face::D, 2), (Face::Dr, 6), (Face::Dbr, 0)]),
+    ("D-Dbr-B", [(Face::D, 4), (Face::Dbr, 8), (Face::B, 2)]),
+    ("D-B-Dbl", [(Face::D, 6), (Face::B, 0), (Face::Dbl, 4)]),
+    ("D-Dbl-Dl", [(Face::D, 8), (Face::Dbl, 2), (Face::Dl, 6)]),
+];
+
+/// Edge positions, named after their faces.
+const EDGES: [(&str, [Position; 2]); 30] = [
+    ("U-Bl", [(Face::U, 1), (Face::Bl, 7)]),
+    ("U-Br", [(Face::U, 3), (Face::Br, 9)]),
+    ("U-R", [(Face::U, 5), (Face::R, 1)]),
+    ("U-F", [(Face::U, 7), (Face::F, 3)]),
+    ("U-L", [(Face::U, 9), (Face::L, 5)]),
+    ("Bl-Dbl", [(Face::Bl, 1), (Face::Dbl, 7)]),
+    ("Bl-B", [(Face::Bl, 3), (Face::B, 7)]),
+    ("Bl-Br", [(Face::Bl, 5), (Face::Br, 1)]),
+    ("Bl-L", [(Face::Bl, 9), (Face::L, 3)]),
+    ("Br-B", [(Face::Br, 3), (Face::B, 5)]),
+    ("Br-Dbr", [(Face::Br, 5), (Face::Dbr, 5)]),
+    ("Br-R", [(Face::Br, 7), (Face::R, 3)]),
+    ("R-Dbr", [(Face::R, 5), (Face::Dbr, 3)]),
+    ("R-Dr", [(Face::R, 7), (Face::Dr, 3)]),
+    ("R-F", [(Face::R, 9), (Face::F, 5)]),
+    ("F-L", [(Face::F, 1), (Face::L, 7)]),
+    ("F-Dr", [(Face::F, 7), (Face::Dr, 1)]),
+    ("F-Dl", [(Face::F, 9), (Face::Dl, 1)]),
+    ("L-Dbl", [(Face::L, 1), (Face::Dbl, 9)]),
+    ("L-Dl", [(Face::L, 9), (Face::Dl, 9)]),
+    ("D-Dr", [(Face::D, 1), (Face::Dr, 7)]),
+    ("D-Dbr", [(Face::D, 3), (Face::Dbr, 9)]),
+    ("D-B", [(Face::D, 5), (Face::B, 1)]),
+    ("D-Dbl", [(Face::D, 7), (Face::Dbl, 3)]),
+    ("D-Dl", [(Face::D, 9), (Face::Dl, 5)]),
+    ("Dr-Dbr", [(Face::Dr, 5), (Face::Dbr, 1)]),
+    ("Dr-Dl", [(Face::Dr, 9), (Face::Dl, 3)]),
+    ("Dbr-B", [(Face::Dbr, 7), (Face::B, 3)]),
+    ("B-Dbl", [(Face::B, 9), (Face::Dbl, 5)]),
+    ("Dbl-Dl", [(Face::Dbl, 1), (Face::Dl, 7)]),
+];
+
+/// Checks that every color is on eleven stickers, and that the state can be reached by turning
+/// a megaminx: the centers must be in a valid orientation, every corner and edge must be a
+/// real piece, and there can be no single twisted corner, no single flipped edge and no two
+/// pieces swapped.
+fn check_stickers(state: &Array2<Face>) -> Result<(), StateError> {
+    let (faces, stickers) = state.dim();
+
+    for face in (0..faces).map(Face::from) {
+        let found = state.iter().filter(|f| **f == face).count();
+        if found != stickers {
+            return Err(StateError::StickerCount {
+                face: face.facelet(),
+                expected: stickers,
+                found,
+            });
+        }
+
+        if !state.slice(s![.., 10]).iter().any(|f| *f == face) {
+            return Err(StateError::Centers);
+        }
+    }
+
+    // Colors are relabelled after the centers, so that a turned megaminx is checked as if it
+    // had been turned back.
+    let center = |f: Face| state[[f as usize, 10]];
+    let frame_fits = CORNERS.iter().all(|(_, stickers)| {
+        let frame = stickers.map(|(f, _)| center(f));
+        CORNERS.iter().any(|(_, corner)| {
+            let faces = corner.map(|(f, _)| f);
+            (0..3).any(|t| (0..3).all(|k| faces[k] == frame[(t + k) % 3]))
+        })
+    });
+    if !frame_fits {
+        return Err(StateError::Centers);
+    }
+
+    let mut relabel = [Face::U; 12];
+    for face in (0..faces).map(Face::from) {
+        relabel[center(face) as usize] = face;
+    }
+    let color = |(f, i): Position| relabel[state[[f as usize, i]] as usize];
+
+    let mut corners = [0; 20];
+    let mut twist = 0;
+    for (i, (position, stickers)) in CORNERS.iter().enumerate() {
+        let colors = stickers.map(color);
+        let (piece, t) = CORNERS
+            .iter()
+            .enumerate()
+            .find_map(|(j, (_, corner))| {
+                let faces = corner.map(|(f, _)| f);
+                (0..3)
+                    .find(|t| (0..3).all(|k| faces[k] == colors[(t + k) % 3]))
+                    .map(|t| (j, t))
+            })
+            .ok_or(StateError::Corner { position })?;
+
+        if corners[..i].contains(&piece) {
+            return Err(StateError::DuplicateCorner { position });
+        }
+        corners[i] = piece;
+        twist += t;
+    }
+    if twist % 3 != 0 {
+        return Err(StateError::CornerTwist);
+    }
+
+    let mut edges = [0; 30];
+    let mut flip = 0;
+    for (i, (position, stickers)) in EDGES.iter().enumerate() {
+        let colors = stickers.map(color);
+        let (piece, f) = EDGES
+            .iter()
+            .enumerate()
+            .find_map(|(j, (_, edge))| {
+                let faces = edge.map(|(f, _)| f);
+                if faces == colors {
+                    Some((j, 0))
+                } else if faces == [colors[1], colors[0]] {
+                    Some((j, 1))
+                } else {
+                    None
+                }
+            })
+            .ok_or(StateError::Edge { position })?;
+
+        if edges[..i].contains(&piece) {
+            return Err(StateError::DuplicateEdge { position });
+        }
+        edges[i] = piece;
+        flip += f;
+    }
+    if flip % 2 != 0 {
+        return Err(StateError::EdgeFlip);
+    }
+
+    // Every turn cycles pieces five at a time, so neither permutation can be odd.
+    if permutation_parity(&corners) || permutation_parity(&edges) {
+        return Err(StateError::Parity);
+    }
+
+    Ok(())
+}
+
+fn centroid(points: &[(f64, f64)]) -> (f64, f64) {
+    let (x, y) = points.iter().fold((0., 0.), |(x, y), p| (x + p.0, y + p.1));
+    (x / points.len() as f64, y / points.len() as f64)
+}
+
+/// Moves every point of a polygon `by` towards its center, leaving small polygons at least a
+/// quarter of their size.
+fn shrink(points: &[(f64, f64)], center: (f64, f64), by: f64) -> Vec<(f64, f64)> {
+    points
+        .iter()
+        .map(|&(x, y)| {
+            let distance = (x - center.0).hypot(y - center.1);
+            let t = (by / distance).min(0.75);
+            (x + (center.0 - x) * t, y + (center.1 - y) * t)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzles::fixtures;
     use ndarray::arr2;
+    use oorandom::Rand32;
 
     use Face::*;
+
+    #[test]
+    fn from_facelets() {
+        let mut mega = Megaminx::new();
+        mega.apply_scramble("R++ D-- R++ D++ R-- D++ U' R-- D-- U");
+
+        assert_eq!(
+            Megaminx::from_facelets(&mega.to_facelets()).unwrap().state,
+            mega.state
+        );
+
+        let solved = Megaminx::new().to_facelets();
+        assert_eq!(&solved[..14], "AAAAAAAAAAABBB");
+
+        let json = Megaminx::new().to_json();
+        assert!(json.starts_with("{\"puzzle\":\"megaminx\",\"faces\":{\"U\":[\"U\","));
+        assert!(json.ends_with("\"Dl\":[\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\",\"Dl\"]}}"));
+        assert_eq!(
+            Megaminx::from_facelets(&solved[1..]).map(|_| ()),
+            Err(StateError::Length {
+                expected: 132,
+                found: 131
+            })
+        );
+        assert_eq!(
+            Megaminx::from_facelets(&solved.replacen('A', "Z", 1)).map(|_| ()),
+            Err(StateError::Facelet {
+                index: 0,
+                found: 'Z'
+            })
+        );
+        assert_eq!(
+            Megaminx::from_facelets(&solved.replacen('A', "B", 1)).map(|_| ()),
+            Err(StateError::StickerCount {
+                face: 'A',
+                expected: 11,
+                found: 10
+            })
+        );
+
+        let mut two_centers: Vec<char> = solved.chars().collect();
+        two_centers.swap(10, 11);
+        assert_eq!(
+            Megaminx::from_facelets(&two_centers.into_iter().collect::<String>()).map(|_| ()),
+            Err(StateError::Centers)
+        );
+
+        for scramble in fixtures::MEGAMINX {
+            let mut mega = Megaminx::new();
+            mega.try_apply_scramble(scramble).unwrap();
+            assert!(Megaminx::from_facelets(&mega.to_facelets()).is_ok());
+        }
+
+        // Stickers are at `face * 11 + index`: the U-R-F corner is at 6, 33 and 48, the U-R
+        // edge at 5 and 34, the U-F edge at 7 and 47.
+        let with = |stickers: &[(usize, char)]| {
+            let mut s: Vec<char> = solved.chars().collect();
+            for &(i, c) in stickers {
+                s[i] = c;
+            }
+            Megaminx::from_facelets(&s.into_iter().collect::<String>()).map(|_| ())
+        };
+        assert_eq!(
+            with(&[(6, 'E'), (33, 'A'), (48, 'D')]),
+            Err(StateError::CornerTwist)
+        );
+        assert_eq!(with(&[(5, 'D'), (34, 'A')]), Err(StateError::EdgeFlip));
+        assert_eq!(with(&[(34, 'E'), (47, 'D')]), Err(StateError::Parity));
+        // U and F centers swapped
+        assert_eq!(with(&[(10, 'E'), (54, 'A')]), Err(StateError::Centers));
+        // Two stickers of the U-R-F corner swapped, mirroring it
+        assert_eq!(
+            with(&[(6, 'D'), (33, 'A')]),
+            Err(StateError::Corner { position: "U-R-F" })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut mega = Megaminx::new();
+        mega.apply_scramble("R++ D-- R++ D++ R-- D++ U' R-- D-- U");
+
+        let json = serde_json::to_string(&mega).unwrap();
+        let de: Megaminx = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(de.state, mega.state);
+        assert_eq!(de.color_scheme.len(), 12);
+
+        let scheme = serde_json::to_string(&mega.get_default_color_scheme()).unwrap();
+        assert!(scheme.starts_with("{\"Megaminx\":{"));
+
+        let mut json: serde_json::Value = serde_json::to_value(&mega).unwrap();
+        json["lettering"] = serde_json::json!({"labels": ["A", "B"]});
+        let error = serde_json::from_value::<Megaminx>(json.clone()).unwrap_err();
+        assert_eq!(error.to_string(), "lettering has 2 labels, expected 120");
+        json["lettering"] = serde_json::Value::Null;
+        json["state"]["dim"] = serde_json::json!([11, 12]);
+        assert!(serde_json::from_value::<Megaminx>(json).is_err());
+    }
+
+    #[test]
+    fn lettering() {
+        let mut mega = Megaminx::new();
+        assert_eq!(mega.draw().to_string().matches("<text").count(), 2);
+
+        mega.set_lettering(Some(LetteringScheme::default()), false);
+        let svg = mega.draw().to_string();
+        assert_eq!(svg.matches("<text").count(), 120);
+        assert!(svg.contains("\nA0\n") && svg.contains("\nL9\n"));
+
+        mega.set_lettering(Some(LetteringScheme::default()), true);
+        assert_eq!(mega.draw().to_string().matches("<text").count(), 132);
+
+        let mut labels = vec!["A".to_string(); 120];
+        labels[0] = "<b>&".to_string();
+        mega.set_lettering(LetteringScheme::new(labels), false);
+        let svg = mega.draw().to_string();
+        assert!(svg.contains("\n&lt;b&gt;&amp;\n"));
+        assert!(!svg.contains("<b>"));
+
+        assert!(LetteringScheme::new(vec!["A".to_string(); 12]).is_none());
+        assert!(LetteringScheme::new(vec!["A".to_string(); 120]).is_some());
+    }
+
+    /// Moves of Pochmann notation with their inverse.
+    const MOVES: [(&str, &str); 6] = [
+        ("R++", "R--"),
+        ("R--", "R++"),
+        ("D++", "D--"),
+        ("D--", "D++"),
+        ("U", "U'"),
+        ("U'", "U"),
+    ];
+
+    #[test]
+    fn move_group_properties() {
+        let mut rng = Rand32::new(12);
+        let solved = Megaminx::new().state;
+
+        for _ in 0..100 {
+            let scramble: Vec<usize> = (0..30).map(|_| rng.rand_range(0..6) as usize).collect();
+            let alg: Vec<&str> = scramble.iter().map(|&i| MOVES[i].0).collect();
+            let inverse: Vec<&str> = scramble.iter().rev().map(|&i| MOVES[i].1).collect();
+            let (mv, inverse_mv) = MOVES[rng.rand_range(0..6) as usize];
+
+            let mut mega = Megaminx::new();
+            mega.try_apply_scramble(&alg.join(" ")).unwrap();
+            let before = mega.state.clone();
+            let context = format!("{mv} after {}", alg.join(" "));
+
+            mega.try_apply_scramble(&format!("{mv} {inverse_mv}"))
+                .unwrap();
+            assert_eq!(mega.state, before, "inverse of {context}");
+
+            mega.try_apply_scramble(&[mv; 5].join(" ")).unwrap();
+            assert_eq!(mega.state, before, "five times {context}");
+
+            mega.try_apply_scramble(mv).unwrap();
+            for face in 0..12 {
+                let count = mega
+                    .state
+                    .iter()
+                    .filter(|f| **f == Face::from(face))
+                    .count();
+                assert_eq!(count, 11, "{} stickers after {context}", Face::from(face));
+            }
+            assert_eq!(check_stickers(&mega.state), Ok(()), "{context}");
+
+            mega.try_apply_scramble(&format!("{inverse_mv} {}", inverse.join(" ")))
+                .unwrap();
+            assert_eq!(mega.state, solved, "{context}");
+        }
+    }
+
     #[test]
     fn megaminx() {
         {
             let mut mega = Megaminx::new();
 
-            mega.apply_scramble(
-                "R-- D++ R++ D-- R-- D++ R-- D-- R++ D-- U' \
-                         R++ D-- R++ D++ R-- D++ R++ D-- R++ D++ U \
-                         R-- D-- R-- D++ R-- D++ R++ D++ R++ D-- U' \
-                         R++ D-- R++ D++ R++ D++ R++ D++ R++ D++ U \
-                         R-- D-- R++ D-- R-- D++ R-- D-- R-- D-- U' \
-                         R-- D-- R-- D-- R++ D-- R-- D++ R++ D++ U \
-                         R++ D-- R-- D++ R++ D-- R++ D-- R++ D++ U",
-            );
+            mega.apply_scramble(fixtures::MEGAMINX[0]);
 
             assert_eq!(
                 mega.state,
@@ -625,16 +1161,7 @@ mod tests {
         {
             let mut mega = Megaminx::new();
 
-            mega.apply_scramble(
-                "
-R++ D-- R++ D-- R-- D++ R++ D-- R-- D-- U'
-R++ D-- R-- D-- R++ D++ R-- D-- R++ D++ U
-R++ D-- R-- D-- R++ D-- R-- D-- R-- D++ U
-R-- D++ R-- D-- R++ D++ R++ D-- R++ D++ U
-R++ D-- R-- D-- R-- D++ R-- D-- R-- D-- U'
-R++ D++ R++ D-- R++ D-- R-- D++ R-- D-- U'
-R++ D++ R-- D-- R-- D++ R-- D-- R-- D-- U'",
-            );
+            mega.apply_scramble(fixtures::MEGAMINX[1]);
 
             assert_eq!(
                 mega.state,
@@ -657,17 +1184,7 @@ R++ D++ R-- D-- R-- D++ R-- D-- R-- D-- U'",
         {
             let mut mega = Megaminx::new();
 
-            mega.apply_scramble(
-                "
-R-- D++ R++ D++ R++ D++ R-- D++ R++ D-- U'
-R++ D-- R-- D++ R++ D-- R++ D++ R++ D-- U'
-R++ D-- R-- D-- R++ D++ R-- D-- R-- D++ U
-R-- D-- R++ D-- R++ D++ R++ D-- R-- D-- U'
-R++ D-- R++ D++ R++ D++ R++ D-- R++ D-- U'
-R++ D-- R-- D++ R++ D-- R-- D++ R++ D-- U'
-R-- D++ R++ D++ R-- D++ R-- D-- R-- D++ U
-",
-            );
+            mega.apply_scramble(fixtures::MEGAMINX[2]);
 
             assert_eq!(
                 mega.state,
@@ -690,17 +1207,7 @@ R-- D++ R++ D++ R-- D++ R-- D-- R-- D++ U
         {
             let mut mega = Megaminx::new();
 
-            mega.apply_scramble(
-                "
-R-- D++ R-- D-- R++ D-- R-- D++ R-- D-- U'
-R-- D-- R++ D++ R-- D-- R-- D-- R++ D++ U
-R++ D++ R-- D-- R-- D-- R++ D++ R-- D-- U'
-R-- D-- R++ D++ R++ D++ R++ D++ R-- D-- U'
-R++ D++ R++ D++ R++ D++ R-- D++ R-- D-- U'
-R++ D-- R++ D++ R++ D-- R-- D-- R-- D++ U
-R++ D-- R-- D++ R-- D++ R-- D-- R-- D++ U
-",
-            );
+            mega.apply_scramble(fixtures::MEGAMINX[3]);
 
             assert_eq!(
                 mega.state,
@@ -723,17 +1230,7 @@ R++ D-- R-- D++ R-- D++ R-- D-- R-- D++ U
         {
             let mut mega = Megaminx::new();
 
-            mega.apply_scramble(
-                "
-R++ D-- R++ D-- R-- D-- R-- D-- R++ D++ U
-R-- D++ R-- D++ R++ D++ R++ D++ R++ D++ U
-R-- D-- R-- D++ R-- D++ R++ D++ R++ D-- U'
-R-- D-- R-- D-- R++ D-- R++ D-- R-- D-- U'
-R++ D-- R++ D-- R-- D-- R-- D-- R-- D++ U
-R++ D++ R++ D++ R++ D-- R-- D++ R-- D-- U'
-R-- D-- R++ D-- R-- D-- R++ D++ R++ D++ U
-",
-            );
+            mega.apply_scramble(fixtures::MEGAMINX[4]);
 
             assert_eq!(
                 mega.state,
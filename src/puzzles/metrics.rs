@@ -0,0 +1,197 @@
+use crate::puzzles::cube::{self, Direction, Face, LayerTurn};
+use crate::puzzles::{megaminx, ScrambleError};
+use std::fmt;
+
+/// Length of an algorithm in each of the usual metrics.
+///
+/// Slices count as the two outer turns they stand for in HTM and QTM, wide moves as the one
+/// face turn they differ from by a rotation. Rotations only count in ETM, and so do moves that
+/// turn a whole number of times like `R4`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveCount {
+    /// Half turn metric, where any turn of the outer layers counts as one.
+    pub htm: usize,
+    /// Quarter turn metric, HTM with half turns counting as two.
+    pub qtm: usize,
+    /// Slice turn metric, where any turn of a layer or a block of layers counts as one.
+    pub stm: usize,
+    /// Execution turn metric, where every move counts as one.
+    pub etm: usize,
+    /// Slice quarter turn metric, STM with half turns counting as two.
+    pub sqtm: usize,
+}
+
+impl MoveCount {
+    /// Counts the moves of an algorithm in cube notation on a cube of `size` layers, which
+    /// tells the rotations and slices apart from the other moves: `3Rw` is a rotation on a 3x3
+    /// and `4R` does not fit.
+    pub fn cube(alg: &str, size: usize) -> Result<MoveCount, ScrambleError> {
+        let moves = cube::parse_scramble_within(alg, |_| size)?;
+        Ok(MoveCount::moves(&moves, |_| size))
+    }
+
+    /// Counts moves on a puzzle with `layers(face)` layers along the axis of each face, as
+    /// they are [`Move::within`] them.
+    pub(crate) fn moves(moves: &[LayerTurn], layers: impl Fn(Face) -> usize) -> MoveCount {
+        let mut count = MoveCount::default();
+
+        for mv in moves {
+            let quarters = match mv.dir {
+                Direction::None => 0,
+                Direction::Half => 2,
+                Direction::Clockwise | Direction::Counterclockwise => 1,
+            };
+            let layers = layers(mv.face);
+            let outer = if mv.is_rotation(layers) {
+                0
+            } else if mv.is_slice(layers) {
+                2
+            } else {
+                1
+            };
+            count.add(quarters, outer);
+        }
+
+        count
+    }
+
+    /// Counts the moves of an algorithm in Pochmann notation. Fifths of a turn count as quarter
+    /// turns do on cubes: two for `R++` and `D++`, one for `U`.
+    pub fn megaminx(alg: &str) -> Result<MoveCount, ScrambleError> {
+        let mut count = MoveCount::default();
+
+        for mv in megaminx::parse_scramble(alg)? {
+            let fifths = if mv.starts_with('U') { 1 } else { 2 };
+            count.add(fifths, 1);
+        }
+
+        Ok(count)
+    }
+
+    /// Counts a move of `quarters` quarter turns, that `outer` turns of the outer layers make.
+    fn add(&mut self, quarters: usize, outer: usize) {
+        let turns = quarters.min(1);
+
+        self.htm += outer * turns;
+        self.qtm += outer * quarters;
+        self.stm += outer.min(1) * turns;
+        self.etm += 1;
+        self.sqtm += outer.min(1) * quarters;
+    }
+
+    /// The counts as a JSON document with a field per metric.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"htm\":{},\"qtm\":{},\"stm\":{},\"etm\":{},\"sqtm\":{}}}",
+            self.htm, self.qtm, self.stm, self.etm, self.sqtm
+        )
+    }
+}
+
+/// The counts as written next to reconstructions, such as `7 HTM, 8 QTM, 7 STM, 7 ETM, 8 SQTM`.
+impl fmt::Display for MoveCount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} HTM, {} QTM, {} STM, {} ETM, {} SQTM",
+            self.htm, self.qtm, self.stm, self.etm, self.sqtm
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(htm: usize, qtm: usize, stm: usize, etm: usize, sqtm: usize) -> MoveCount {
+        MoveCount {
+            htm,
+            qtm,
+            stm,
+            etm,
+            sqtm,
+        }
+    }
+
+    #[test]
+    fn cube() {
+        assert_eq!(MoveCount::cube("", 3), Ok(MoveCount::default()));
+        assert_eq!(
+            MoveCount::cube("R U R' U R U2 R'", 3),
+            Ok(count(7, 8, 7, 7, 8))
+        );
+        assert_eq!(
+            MoveCount::cube("M2 U M2 U2 M2 U M2", 3),
+            Ok(count(11, 20, 7, 7, 12))
+        );
+        assert_eq!(
+            MoveCount::cube("x Rw U 2R' 3Fw2", 4),
+            Ok(count(5, 6, 4, 5, 5))
+        );
+        assert_eq!(
+            MoveCount::cube("y' Rw U R' U' Rw' F R F'", 3),
+            Ok(count(8, 8, 8, 9, 8))
+        );
+        assert_eq!(MoveCount::cube("E2 S' 3R2", 5), Ok(count(6, 10, 3, 3, 5)));
+        assert_eq!(MoveCount::cube("R4 U", 3), Ok(count(1, 1, 1, 2, 1)));
+
+        // Moves are what they turn on the cube they are counted on.
+        assert_eq!(MoveCount::cube("3Rw 3Uw'", 3), Ok(count(0, 0, 0, 2, 0)));
+        assert_eq!(MoveCount::cube("3R2 2Rw", 3), Ok(count(2, 3, 2, 2, 3)));
+        assert_eq!(MoveCount::cube("Rw", 2), Ok(count(0, 0, 0, 1, 0)));
+        assert_eq!(
+            MoveCount::cube("R 4R", 3),
+            Err(ScrambleError::Layer {
+                index: 1,
+                found: "4R".to_string(),
+                layers: 3
+            })
+        );
+        assert_eq!(
+            MoveCount::cube("M", 4),
+            Err(ScrambleError::Layer {
+                index: 0,
+                found: "M".to_string(),
+                layers: 4
+            })
+        );
+
+        assert_eq!(
+            MoveCount::cube("R U%", 3),
+            Err(ScrambleError::Notation {
+                index: 1,
+                found: "U%".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn puzzles() {
+        let moves = |event: &str, alg: &str| crate::puzzles::new(event).unwrap().move_count(alg);
+
+        assert_eq!(moves("333", "3Rw R"), Ok(count(1, 1, 1, 2, 1)));
+        assert_eq!(moves("332", "2Uw R2 U"), Ok(count(2, 3, 2, 3, 3)));
+        assert_eq!(
+            moves("332", "U R"),
+            Err(ScrambleError::QuarterTurn {
+                index: 1,
+                found: "R".to_string()
+            })
+        );
+        assert_eq!(moves("MEGA", "R++ U"), Ok(count(2, 3, 2, 2, 3)));
+    }
+
+    #[test]
+    fn megaminx() {
+        let moves = MoveCount::megaminx("R++ D-- R-- D++ U'").unwrap();
+        assert_eq!(moves, count(5, 9, 5, 5, 9));
+        assert_eq!(moves.to_string(), "5 HTM, 9 QTM, 5 STM, 5 ETM, 9 SQTM");
+        assert_eq!(
+            moves.to_json(),
+            "{\"htm\":5,\"qtm\":9,\"stm\":5,\"etm\":5,\"sqtm\":9}"
+        );
+
+        assert!(MoveCount::megaminx("R++ R").is_err());
+    }
+}
@@ -1,39 +1,307 @@
 use crate::puzzles::cube::{
     Cube, FiveByFive, FourByFour, SevenBySeven, SixBySix, ThreeByThree, TwoByTwo,
 };
+use crate::puzzles::cuboid::{Cuboid, ThreeByThreeByFour, ThreeByThreeByTwo, TwoByTwoByThree};
 use crate::puzzles::megaminx::Megaminx;
+use crate::puzzles::metrics::MoveCount;
 use crate::utils::color::Color;
+use lazy_static::lazy_static;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use svg::node::element::SVG;
 
-mod cube;
-mod megaminx;
+pub mod alg;
+pub mod bld;
+pub mod cases;
+pub mod cube;
+pub mod cuboid;
+mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod megaminx;
+pub mod metrics;
+mod net;
+pub mod reconstruction;
+mod render;
+pub mod sheet;
+
+pub use error::{AlgError, ScrambleError, SheetError, StateError};
+pub use render::{register_theme, theme, themes, Accessibility, RenderOptions};
 
 pub trait Puzzle {
     fn new() -> Self
     where
         Self: Sized;
 
-    fn apply_scramble(&mut self, scramble: &str);
+    /// Makes every move of the scramble, or none of them if one cannot be read or made.
+    fn try_apply_scramble(&mut self, scramble: &str) -> Result<(), ScrambleError>;
+
+    /// Makes every move of the scramble, leaving the puzzle as it was if one cannot be read or
+    /// made. [`Puzzle::try_apply_scramble`] tells which one.
+    fn apply_scramble(&mut self, scramble: &str) {
+        let _ = self.try_apply_scramble(scramble);
+    }
     fn get_default_color_scheme(&self) -> ColorSchemes;
 
-    fn draw(&self) -> SVG;
+    /// Length of an algorithm in each of the usual metrics, with its moves read as
+    /// [`Puzzle::try_apply_scramble`] reads them.
+    fn move_count(&self, alg: &str) -> Result<MoveCount, ScrambleError>;
+
+    /// The current state as a facelet string, in the format read by `from_facelets`.
+    fn to_facelets(&self) -> String;
+    /// The current state as a JSON document with the puzzle type, its size if it comes in
+    /// several, and the stickers of each face.
+    fn to_json(&self) -> String;
+
+    fn draw(&self) -> SVG {
+        self.draw_with(&RenderOptions::default())
+    }
+    fn draw_with(&self, options: &RenderOptions) -> SVG;
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColorSchemes {
     Cube(HashMap<cube::Face, Color>),
     Megaminx(HashMap<megaminx::Face, Color>),
 }
 
+/// The JSON document of [`Puzzle::to_json`], leaving out the size of puzzles that only come in
+/// one.
+fn state_json(puzzle: &str, size: Option<&str>, faces: &[(String, Vec<String>)]) -> String {
+    let faces: Vec<String> = faces
+        .iter()
+        .map(|(face, stickers)| {
+            let stickers: Vec<String> = stickers.iter().map(|s| format!("\"{s}\"")).collect();
+            format!("\"{face}\":[{}]", stickers.join(","))
+        })
+        .collect();
+
+    let size = match size {
+        Some(size) => format!(",\"size\":{size}"),
+        None => String::new(),
+    };
+    format!(
+        "{{\"puzzle\":\"{puzzle}\"{size},\"faces\":{{{}}}}}",
+        faces.join(",")
+    )
+}
+
+/// Builds a new puzzle for a registered event.
+pub type Factory = dyn Fn() -> Box<dyn Puzzle> + Send + Sync;
+
+struct Event {
+    id: String,
+    aliases: Vec<String>,
+    factory: Arc<Factory>,
+}
+
+lazy_static! {
+    static ref REGISTRY: RwLock<Vec<Event>> = {
+        let builtin: [(&str, &[&str], Arc<Factory>); 10] = [
+            (
+                "333",
+                &["OH", "3BLD"],
+                Arc::new(|| Box::new(Cube::<ThreeByThree>::new())),
+            ),
+            ("222", &[], Arc::new(|| Box::new(Cube::<TwoByTwo>::new()))),
+            ("444", &[], Arc::new(|| Box::new(Cube::<FourByFour>::new()))),
+            ("555", &[], Arc::new(|| Box::new(Cube::<FiveByFive>::new()))),
+            ("666", &[], Arc::new(|| Box::new(Cube::<SixBySix>::new()))),
+            (
+                "777",
+                &[],
+                Arc::new(|| Box::new(Cube::<SevenBySeven>::new())),
+            ),
+            ("MEGA", &[], Arc::new(|| Box::new(Megaminx::new()))),
+            (
+                "223",
+                &[],
+                Arc::new(|| Box::new(Cuboid::<TwoByTwoByThree>::new())),
+            ),
+            (
+                "332",
+                &[],
+                Arc::new(|| Box::new(Cuboid::<ThreeByThreeByTwo>::new())),
+            ),
+            (
+                "334",
+                &[],
+                Arc::new(|| Box::new(Cuboid::<ThreeByThreeByFour>::new())),
+            ),
+        ];
+
+        RwLock::new(
+            builtin
+                .into_iter()
+                .map(|(id, aliases, factory)| Event {
+                    id: id.to_string(),
+                    aliases: aliases.iter().map(|a| a.to_string()).collect(),
+                    factory,
+                })
+                .collect(),
+        )
+    };
+}
+
+/// Registers a puzzle under an event id and its aliases, so that it can be built with [`new`].
+///
+/// Registering an id that already exists replaces it, ids and aliases taken as aliases by
+/// other events are moved to this one.
+pub fn register<F>(id: &str, aliases: &[&str], factory: F)
+where
+    F: Fn() -> Box<dyn Puzzle> + Send + Sync + 'static,
+{
+    let mut registry = REGISTRY.write().unwrap();
+
+    registry.retain(|e| e.id != id);
+    for event in registry.iter_mut() {
+        event
+            .aliases
+            .retain(|a| a != id && !aliases.contains(&a.as_str()));
+    }
+
+    registry.push(Event {
+        id: id.to_string(),
+        aliases: aliases.iter().map(|a| a.to_string()).collect(),
+        factory: Arc::new(factory),
+    });
+}
+
+/// Ids of the registered events, in registration order.
+pub fn events() -> Vec<String> {
+    REGISTRY
+        .read()
+        .unwrap()
+        .iter()
+        .map(|e| e.id.clone())
+        .collect()
+}
+
+/// Builds the puzzle of an event, looked up by id first and then by alias.
 pub fn new(event: &str) -> Option<Box<dyn Puzzle>> {
-    match event {
-        "333" | "OH" | "3BLD" => Some(Box::new(Cube::<ThreeByThree>::new())),
-        "222" => Some(Box::new(Cube::<TwoByTwo>::new())),
-        "444" => Some(Box::new(Cube::<FourByFour>::new())),
-        "555" => Some(Box::new(Cube::<FiveByFive>::new())),
-        "666" => Some(Box::new(Cube::<SixBySix>::new())),
-        "777" => Some(Box::new(Cube::<SevenBySeven>::new())),
-        "MEGA" => Some(Box::new(Megaminx::new())),
-        _ => None,
+    let registry = REGISTRY.read().unwrap();
+    let factory = registry
+        .iter()
+        .find(|e| e.id == event)
+        .or_else(|| {
+            registry
+                .iter()
+                .find(|e| e.aliases.iter().any(|a| a == event))
+        })
+        .map(|e| e.factory.clone())?;
+    drop(registry);
+
+    Some(factory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The registry is shared by every test running at the same time, so these only register
+    // ids of their own and leave the built-in events as they are.
+
+    #[test]
+    fn registry() {
+        assert!(new("OH").is_some());
+        assert!(new("test-registry-ft").is_none());
+
+        register("test-registry-ft", &["test-registry-FT"], || {
+            Box::new(Cube::<ThreeByThree>::new())
+        });
+
+        assert!(events().contains(&"test-registry-ft".to_string()));
+        assert!(new("test-registry-ft").is_some());
+        assert!(new("test-registry-FT").is_some());
+
+        register("test-registry-ft", &[], || Box::new(Megaminx::new()));
+        assert_eq!(
+            events().iter().filter(|e| *e == "test-registry-ft").count(),
+            1
+        );
+        assert!(new("test-registry-FT").is_none());
+        assert_eq!(
+            new("test-registry-ft").unwrap().to_facelets(),
+            Megaminx::new().to_facelets()
+        );
+
+        register("test-registry-fm", &["test-registry-FMC"], || {
+            Box::new(Cube::<ThreeByThree>::new())
+        });
+        register("test-registry-mbf", &["test-registry-FMC"], || {
+            Box::new(Cube::<ThreeByThree>::new())
+        });
+        assert!(new("test-registry-FMC").is_some());
+
+        {
+            let registry = REGISTRY.read().unwrap();
+            let fm = registry
+                .iter()
+                .find(|e| e.id == "test-registry-fm")
+                .unwrap();
+            assert!(fm.aliases.is_empty());
+        }
+
+        register("test-registry-bld", &["test-registry-BLD"], || {
+            Box::new(Cube::<ThreeByThree>::new())
+        });
+        register("test-registry-BLD", &[], || Box::new(Megaminx::new()));
+        assert_eq!(
+            new("test-registry-BLD").unwrap().to_facelets(),
+            Megaminx::new().to_facelets()
+        );
+        let registry = REGISTRY.read().unwrap();
+        let bld = registry
+            .iter()
+            .find(|e| e.id == "test-registry-bld")
+            .unwrap();
+        assert!(!bld.aliases.contains(&"test-registry-BLD".to_string()));
+    }
+
+    #[test]
+    fn ids_before_aliases() {
+        register("test-registry-555", &[], || {
+            Box::new(Cube::<FiveByFive>::new())
+        });
+        register("test-registry-mts", &["test-registry-555"], || {
+            Box::new(Megaminx::new())
+        });
+        assert_eq!(
+            new("test-registry-555").unwrap().to_facelets(),
+            Cube::<FiveByFive>::new().to_facelets()
+        );
+        assert_eq!(
+            new("test-registry-mts").unwrap().to_facelets(),
+            Megaminx::new().to_facelets()
+        );
+    }
+
+    #[test]
+    fn malformed_scrambles() {
+        let scrambles = [
+            "R U x",
+            "R+ U-",
+            "8R 8U",
+            "R2 U2 F",
+            "R++ D-- U''",
+            "Rw2 \u{0} 3Fw'",
+            "UUUU 999R",
+            "é ñ",
+            "'",
+        ];
+
+        for event in events() {
+            for scramble in scrambles {
+                let mut puzzle = new(&event).unwrap();
+                let solved = puzzle.to_facelets();
+
+                if puzzle.try_apply_scramble(scramble).is_err() {
+                    assert_eq!(puzzle.to_facelets(), solved, "{event} {scramble}");
+                    puzzle.apply_scramble(scramble);
+                    assert_eq!(puzzle.to_facelets(), solved, "{event} {scramble}");
+                }
+                puzzle.draw();
+            }
+        }
     }
 }
@@ -0,0 +1,474 @@
+use crate::puzzles::cube::{Direction, Face, LayerTurn, Sticker};
+use crate::puzzles::render::{body, mark_face, Stickers};
+use crate::puzzles::RenderOptions;
+use crate::utils::color::Color;
+use crate::utils::maths::rotate_2d_matrix;
+use ndarray::{s, Array2};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use svg::node::element::Group;
+use svg::Node;
+
+/// The six faces of a cube or a cuboid, given by its dimensions in notation order: width,
+/// depth and height.
+///
+/// Faces are laid out as in the net: U and D have `depth` rows of `width` stickers, F and B
+/// `height` rows of `width`, R and L `height` rows of `depth`. Flat states have the stickers
+/// of each face row by row, faces in the order of [`Face`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Net {
+    pub width: usize,
+    pub depth: usize,
+    pub height: usize,
+}
+
+impl Net {
+    pub const fn cube(size: usize) -> Self {
+        Net {
+            width: size,
+            depth: size,
+            height: size,
+        }
+    }
+
+    /// Rows and columns of a face.
+    pub fn shape(self, face: Face) -> (usize, usize) {
+        match face {
+            Face::U | Face::D => (self.depth, self.width),
+            Face::F | Face::B => (self.height, self.width),
+            Face::R | Face::L => (self.height, self.depth),
+        }
+    }
+
+    /// Layers turned by the moves of a face.
+    pub fn layers(self, face: Face) -> usize {
+        match face {
+            Face::U | Face::D => self.height,
+            Face::R | Face::L => self.width,
+            Face::F | Face::B => self.depth,
+        }
+    }
+
+    /// Index of a sticker in a flat state.
+    fn index(self, face: Face, row: usize, col: usize) -> usize {
+        let before: usize = (0..face as usize)
+            .map(|f| {
+                let (rows, cols) = self.shape(Face::from(f));
+                rows * cols
+            })
+            .sum();
+        before + row * self.shape(face).1 + col
+    }
+
+    /// Top left corner of every face in the net, with stickers of `sticker_size` and `gap`
+    /// between faces.
+    pub fn positions(self, sticker_size: f64, gap: f64) -> [(f64, f64, Face); 6] {
+        let (width, depth, height) = (
+            self.width as f64 * sticker_size,
+            self.depth as f64 * sticker_size,
+            self.height as f64 * sticker_size,
+        );
+
+        [
+            (3. * gap + depth + width, 2. * gap + depth, Face::R),
+            (2. * gap + depth, gap, Face::U),
+            (2. * gap + depth, 2. * gap + depth, Face::F),
+            (gap, 2. * gap + depth, Face::L),
+            (2. * gap + depth, 3. * gap + depth + height, Face::D),
+            (4. * gap + 2. * depth + width, 2. * gap + depth, Face::B),
+        ]
+    }
+
+    /// Width and height of the net.
+    pub fn size(self, sticker_size: f64, gap: f64) -> (f64, f64) {
+        (
+            2. * (self.width + self.depth) as f64 * sticker_size + 5. * gap,
+            (2 * self.depth + self.height) as f64 * sticker_size + 4. * gap,
+        )
+    }
+}
+
+/// Sticker size and gap between faces, from the options or the puzzle's own.
+pub(crate) fn layout(options: &RenderOptions, sticker_size: f64, gap: f64) -> (f64, f64) {
+    (
+        options.sticker_size.unwrap_or(sticker_size),
+        options.gap.unwrap_or(gap),
+    )
+}
+
+/// Every face with its index and color, from the options or the puzzle's own.
+pub(crate) fn face_colors<'a>(
+    color_scheme: &'a HashMap<Face, Color>,
+    options: &'a RenderOptions,
+) -> impl Iterator<Item = (usize, Face, Color)> + 'a {
+    let color_scheme = options.cube_colors.as_ref().unwrap_or(color_scheme);
+
+    (0..6).map(|i| {
+        let face = Face::from(i);
+        let color = color_scheme.get(&face).copied().unwrap_or(Color::black());
+        (i, face, color)
+    })
+}
+
+/// Draws the faces of a net for one kind of puzzle.
+pub(crate) struct Painter<'a> {
+    /// Name of the puzzle, for the ids of its patterns.
+    pub puzzle: &'a str,
+    pub sticker_size: f64,
+    pub options: &'a RenderOptions,
+}
+
+impl Painter<'_> {
+    /// Draws a face of `rows` by `cols` stickers from its top left corner. `sticker` gives the
+    /// color of each and the face it belongs on, `None` for hidden stickers, and `over` draws
+    /// on top of each once it is drawn.
+    pub fn face(
+        &self,
+        g: &mut Group,
+        (x, y): (f64, f64),
+        (rows, cols): (usize, usize),
+        sticker: impl Fn(usize, usize) -> (Color, Option<Face>),
+        mut over: impl FnMut(&mut Group, usize, usize, (f64, f64), Color),
+    ) {
+        let size = self.sticker_size;
+        if let Some(body) = body(x, y, cols as f64 * size, rows as f64 * size, self.options) {
+            g.append(body);
+        }
+
+        let mut stickers = Stickers::new(g, self.options);
+        for row in 0..rows {
+            for col in 0..cols {
+                let (x, y) = (x + col as f64 * size, y + row as f64 * size);
+                let (color, face) = sticker(row, col);
+
+                stickers.rect(g, (x, y), (size, size), color);
+                if let Some(face) = face {
+                    mark_face(g, self.puzzle, face, (x, y), size, color, self.options);
+                }
+                over(g, row, col, (x, y), color);
+            }
+        }
+        stickers.finish(g);
+    }
+}
+
+/// Nets of the puzzles of the crate, each with the tables of its moves in [`TABLES`].
+const NETS: [Net; 9] = [
+    Net::cube(2),
+    Net::cube(3),
+    Net::cube(4),
+    Net::cube(5),
+    Net::cube(6),
+    Net::cube(7),
+    Net {
+        width: 2,
+        depth: 2,
+        height: 3,
+    },
+    Net {
+        width: 3,
+        depth: 3,
+        height: 2,
+    },
+    Net {
+        width: 3,
+        depth: 3,
+        height: 4,
+    },
+];
+
+/// Tables of every move of each of [`NETS`], built when the first of its puzzles turns.
+static TABLES: [OnceLock<MoveTables>; NETS.len()] = [const { OnceLock::new() }; NETS.len()];
+
+/// The table of a move that fits the puzzle, `None` for moves that do not turn. Puzzles of
+/// sizes from outside the crate get a new table for every move.
+pub(crate) fn move_table(net: Net, mv: &LayerTurn) -> Option<Cow<'static, MoveTable>> {
+    let quarter_turns = match mv.dir {
+        Direction::None => return None,
+        Direction::Clockwise => 1,
+        Direction::Half => 2,
+        Direction::Counterclockwise => 3,
+    };
+
+    match NETS.iter().position(|n| *n == net) {
+        Some(i) => {
+            let tables = TABLES[i].get_or_init(|| MoveTables::new(net));
+            tables.get(mv, quarter_turns).map(Cow::Borrowed)
+        }
+        None => Some(Cow::Owned(MoveTable::new(net, mv, quarter_turns))),
+    }
+}
+
+/// The tables of every move of a puzzle, by face, depth, wideness and quarter turns.
+struct MoveTables {
+    /// Depths a move can have on any face.
+    depths: usize,
+    /// `None` for quarter turns of faces that are not square, which cannot be made.
+    tables: Vec<Option<MoveTable>>,
+}
+
+impl MoveTables {
+    fn new(net: Net) -> Self {
+        let depths = net.width.max(net.depth).max(net.height);
+        let mut tables = Vec::with_capacity(6 * depths * 2 * 3);
+        for face in [0, 1, 2, 3, 4, 5].map(Face::from) {
+            let (rows, cols) = net.shape(face);
+            for depth in 0..depths {
+                for wide in [false, true] {
+                    for quarter_turns in 1..=3 {
+                        let mv = LayerTurn {
+                            face,
+                            dir: Direction::from(quarter_turns as usize),
+                            depth,
+                            wide,
+                        };
+                        let fits = depth < net.layers(face) && (rows == cols || quarter_turns == 2);
+                        tables.push(fits.then(|| MoveTable::new(net, &mv, quarter_turns)));
+                    }
+                }
+            }
+        }
+        MoveTables { depths, tables }
+    }
+
+    fn get(&self, mv: &LayerTurn, quarter_turns: u8) -> Option<&MoveTable> {
+        if mv.depth >= self.depths {
+            return None;
+        }
+        let i = ((mv.face as usize * self.depths + mv.depth) * 2 + mv.wide as usize) * 3
+            + quarter_turns as usize
+            - 1;
+        self.tables.get(i)?.as_ref()
+    }
+}
+
+/// A move as a permutation of the stickers, split into cycles so that it can be applied in
+/// place. Every sticker of a cycle is replaced by the next one, turned by its twist, and the
+/// last one by the first.
+#[derive(Debug, Clone)]
+pub(crate) struct MoveTable {
+    /// Indices of the stickers in the flat state, one cycle after the other.
+    cycles: Vec<usize>,
+    /// Length of each cycle.
+    lengths: Vec<usize>,
+    /// Quarter turns clockwise given to the sticker landing on each index of `cycles`.
+    twists: Vec<u8>,
+}
+
+impl MoveTable {
+    /// Builds the table by making the move on a puzzle where every sticker remembers where it
+    /// came from.
+    pub fn new(net: Net, mv: &LayerTurn, quarter_turns: u8) -> Self {
+        let mut faces = [0, 1, 2, 3, 4, 5].map(|f| {
+            let face = Face::from(f);
+            Array2::from_shape_fn(net.shape(face), |(row, col)| Sticker {
+                face,
+                row,
+                col,
+                rotation: 0,
+            })
+        });
+        turn(net, &mut faces, mv, quarter_turns as usize);
+
+        let source: Vec<(usize, u8)> = faces
+            .iter()
+            .flatten()
+            .map(|s| (net.index(s.face, s.row, s.col), s.rotation))
+            .collect();
+
+        let mut table = MoveTable {
+            cycles: Vec::new(),
+            lengths: Vec::new(),
+            twists: Vec::new(),
+        };
+        let mut visited = vec![false; source.len()];
+        for start in 0..source.len() {
+            if visited[start] || source[start] == (start, 0) {
+                continue;
+            }
+
+            let mut i = start;
+            let mut length = 0;
+            while !visited[i] {
+                visited[i] = true;
+                table.cycles.push(i);
+                table.twists.push(source[i].1);
+                length += 1;
+                i = source[i].0;
+            }
+            table.lengths.push(length);
+        }
+        table
+    }
+
+    pub fn apply<E: Turnable>(&self, stickers: &mut [E]) {
+        let mut start = 0;
+        for &length in &self.lengths {
+            let cycle = &self.cycles[start..start + length];
+            let twists = &self.twists[start..start + length];
+
+            let first = stickers[cycle[0]];
+            for j in 1..length {
+                stickers[cycle[j - 1]] = stickers[cycle[j]].turned(twists[j - 1]);
+            }
+            stickers[cycle[length - 1]] = first.turned(twists[length - 1]);
+
+            start += length;
+        }
+    }
+}
+
+/// What a sticker carries through moves. Stickers can come out of a move rotated compared to
+/// the net, which only matters when tracking orientation.
+pub(crate) trait Turnable: Copy {
+    fn turned(self, quarter_turns: u8) -> Self;
+}
+
+impl Turnable for Face {
+    fn turned(self, _: u8) -> Self {
+        self
+    }
+}
+
+impl Turnable for Sticker {
+    fn turned(self, quarter_turns: u8) -> Self {
+        Sticker {
+            rotation: (self.rotation + quarter_turns) % 4,
+            ..self
+        }
+    }
+}
+
+/// Positions of a row or column of stickers, in the order they move.
+type Strip = Vec<(Face, usize, usize)>;
+
+/// Turns the layers of a move by `quarter_turns` clockwise. Quarter turns of faces that are
+/// not square cannot be made, the caller has to check for them.
+fn turn<E: Turnable>(net: Net, faces: &mut [Array2<E>; 6], mv: &LayerTurn, quarter_turns: usize) {
+    // The face turns with its layer, and the opposite face turns the other way when the
+    // last layer is part of the move.
+    if mv.depth == 0 || mv.wide {
+        rotate_face(&mut faces[mv.face as usize], quarter_turns);
+    }
+    if mv.depth == net.layers(mv.face) - 1 {
+        rotate_face(&mut faces[mv.face.opposite() as usize], 4 - quarter_turns);
+    }
+
+    let first = if mv.wide { 0 } else { mv.depth };
+    for layer in first..=mv.depth {
+        let (ring, twists, forward) = match mv.face {
+            Face::U | Face::D => ud_ring(net, mv.face, layer),
+            Face::R | Face::L => rl_ring(net, mv.face, layer),
+            Face::F | Face::B => fb_ring(net, mv.face, layer),
+        };
+        let shift = if forward {
+            quarter_turns
+        } else {
+            4 - quarter_turns
+        };
+        cycle(faces, ring, twists, shift);
+    }
+}
+
+fn rotate_face<E: Turnable>(face: &mut Array2<E>, quarter_turns: usize) {
+    let rotated = match quarter_turns % 4 {
+        1 => rotate_2d_matrix(face, true),
+        2 => face.slice(s![..;-1, ..;-1]).to_owned(),
+        _ => rotate_2d_matrix(face, false),
+    };
+    *face = rotated.mapv(|e| e.turned(quarter_turns as u8 % 4));
+}
+
+fn row(net: Net, face: Face, row: usize, reversed: bool) -> Strip {
+    let mut strip: Strip = (0..net.shape(face).1).map(|col| (face, row, col)).collect();
+    if reversed {
+        strip.reverse();
+    }
+    strip
+}
+
+fn col(net: Net, face: Face, col: usize, reversed: bool) -> Strip {
+    let mut strip: Strip = (0..net.shape(face).0).map(|row| (face, row, col)).collect();
+    if reversed {
+        strip.reverse();
+    }
+    strip
+}
+
+/// Moves each strip of the ring `shift` places forward, turning its stickers by the twists of
+/// the steps they take. Strips moved onto each other must have the same length, which quarter
+/// turns only guarantee on square faces.
+fn cycle<E: Turnable>(faces: &mut [Array2<E>; 6], ring: [Strip; 4], twists: [u8; 4], shift: usize) {
+    let stickers: Vec<Vec<E>> = ring
+        .iter()
+        .map(|strip| {
+            strip
+                .iter()
+                .map(|(f, row, col)| faces[*f as usize][[*row, *col]])
+                .collect()
+        })
+        .collect();
+
+    for (i, stickers) in stickers.into_iter().enumerate() {
+        let twist: u8 = (i..i + shift).map(|step| twists[step % 4]).sum();
+        for ((f, row, col), sticker) in ring[(i + shift) % 4].iter().zip(stickers) {
+            faces[*f as usize][[*row, *col]] = sticker.turned(twist % 4);
+        }
+    }
+}
+
+/// The ring of a layer of the U and D axis, which moves forward on U turns. Stickers keep
+/// their orientation in the net.
+fn ud_ring(net: Net, face: Face, layer: usize) -> ([Strip; 4], [u8; 4], bool) {
+    let r = if face == Face::U {
+        layer
+    } else {
+        net.height - layer - 1
+    };
+
+    let ring = [
+        row(net, Face::F, r, false),
+        row(net, Face::L, r, false),
+        row(net, Face::B, r, false),
+        row(net, Face::R, r, false),
+    ];
+    (ring, [0; 4], face == Face::U)
+}
+
+/// The ring of a layer of the R and L axis, which moves forward on R turns. Stickers moved
+/// between U, F and D keep their orientation in the net, while those going through B are
+/// turned upside down.
+fn rl_ring(net: Net, face: Face, layer: usize) -> ([Strip; 4], [u8; 4], bool) {
+    let [c, back] = if face == Face::R {
+        [net.width - layer - 1, layer]
+    } else {
+        [layer, net.width - layer - 1]
+    };
+
+    let ring = [
+        col(net, Face::F, c, false),
+        col(net, Face::U, c, false),
+        col(net, Face::B, back, true),
+        col(net, Face::D, c, false),
+    ];
+    (ring, [0, 2, 2, 0], face == Face::R)
+}
+
+/// The ring of a layer of the F and B axis, which moves forward on F turns. Stickers are
+/// turned a quarter clockwise in the net at every step.
+fn fb_ring(net: Net, face: Face, layer: usize) -> ([Strip; 4], [u8; 4], bool) {
+    let [near, far] = if face == Face::F {
+        [layer, net.depth - layer - 1]
+    } else {
+        [net.depth - layer - 1, layer]
+    };
+
+    let ring = [
+        row(net, Face::U, far, false),
+        col(net, Face::R, near, false),
+        row(net, Face::D, near, true),
+        col(net, Face::L, far, true),
+    ];
+    (ring, [1; 4], face == Face::F)
+}
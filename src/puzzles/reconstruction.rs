@@ -0,0 +1,178 @@
+use crate::puzzles::alg::Alg;
+use crate::puzzles::cube::Cube;
+use crate::puzzles::render::{document, embed, escape, size};
+use crate::puzzles::{Puzzle, RenderOptions, ScrambleError};
+use crate::utils::color::Color;
+use svg::node::element::{Text, SVG};
+use svg::Node;
+
+impl<T> Cube<T>
+where
+    usize: From<T>,
+    T: Default,
+{
+    /// Draws the cube as it is and after every step of the solution, `columns` to a row, with
+    /// the label of each step and its length in HTM underneath. Steps without a label are
+    /// numbered.
+    ///
+    /// The cube is left as it is. Errors give the index of the move at fault in the whole
+    /// solution.
+    pub fn draw_solution(
+        &self,
+        solution: &Alg,
+        columns: usize,
+        options: &RenderOptions,
+    ) -> Result<SVG, ScrambleError> {
+        let thumbnail_options = RenderOptions {
+            background: None,
+            ..options.clone()
+        };
+
+        let mut cube = self.clone();
+        let mut thumbnails = vec![(
+            "Scramble".to_string(),
+            None,
+            self.draw_with(&thumbnail_options),
+        )];
+        let mut done = 0;
+        for (i, step) in solution.steps.iter().enumerate() {
+            let moves = step.to_string();
+            cube.try_apply_scramble(&moves)
+                .map_err(|e| e.offset(done))?;
+            done += step.moves.len();
+
+            let label = match &step.label {
+                Some(label) => label.clone(),
+                None => format!("Step {}", i + 1),
+            };
+            let count = cube
+                .move_count(&moves)
+                .expect("moves that were made can be counted");
+            thumbnails.push((label, Some(count), cube.draw_with(&thumbnail_options)));
+        }
+
+        let (width, height) = size(&thumbnails[0].2);
+        // About a sticker high on a 3x3.
+        let line_height = width / 13.;
+        let cell_width = width + line_height;
+        let cell_height = height + 3. * line_height;
+
+        let columns = columns.clamp(1, thumbnails.len());
+        let rows = thumbnails.len().div_ceil(columns);
+
+        let mut svg = document(
+            columns as f64 * cell_width,
+            rows as f64 * cell_height,
+            options,
+            "Reconstruction",
+            || describe(solution),
+        );
+        let color = match options.background {
+            Some(background) => background.contrasting(),
+            None => Color::black(),
+        };
+
+        for (i, (label, count, thumbnail)) in thumbnails.into_iter().enumerate() {
+            let x = (i % columns) as f64 * cell_width;
+            let y = (i / columns) as f64 * cell_height;
+            svg.append(embed(thumbnail, x + line_height / 2., y, width, height));
+
+            let lines = [Some(label), count.map(|c| format!("{} HTM", c.htm))];
+            for (j, line) in lines.into_iter().flatten().enumerate() {
+                svg.append(
+                    Text::new()
+                        .set("x", x + cell_width / 2.)
+                        .set("y", y + height + (j + 1) as f64 * line_height)
+                        .set("text-anchor", "middle")
+                        .set("font-family", "monospace")
+                        .set("font-size", line_height * 0.8)
+                        .set("fill", color.to_string())
+                        .add(svg::node::Text::new(escape(&line))),
+                );
+            }
+        }
+
+        Ok(svg)
+    }
+}
+
+/// Every step with its moves, for screen readers.
+fn describe(solution: &Alg) -> String {
+    let steps: Vec<String> = solution
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| match &step.label {
+            Some(label) => format!("{label}: {step}."),
+            None => format!("Step {}: {step}.", i + 1),
+        })
+        .collect();
+    steps.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::{FourByFour, ThreeByThree};
+    use crate::puzzles::Accessibility;
+
+    const SOLVE: &str = "\
+        y' R' F R // cross\n\
+        U R U' R' // pair 1\n\
+        (R U R' U')2\n\
+        [F: [R, U]] // OLL\n";
+
+    #[test]
+    fn thumbnails() {
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.try_apply_scramble("F R U").unwrap();
+        let scrambled = cube.to_facelets();
+        let solution = Alg::parse(SOLVE).unwrap();
+
+        let svg = cube
+            .draw_solution(&solution, 3, &RenderOptions::default())
+            .unwrap()
+            .to_string();
+        assert_eq!(svg.matches("<svg").count(), 6);
+        assert_eq!(svg.matches("<rect").count(), 5 * 54);
+        for text in [
+            "Scramble", "cross", "3 HTM", "pair 1", "Step 3", "8 HTM", "OLL", "6 HTM",
+        ] {
+            assert!(svg.contains(&format!(">\n{text}\n</text>")), "{text}");
+        }
+        // Nets of 130 by 98 with room for their captions, three to a row.
+        assert!(svg.contains("viewBox=\"0 0 420 256\""));
+
+        let svg = cube
+            .draw_solution(&solution, 10, &RenderOptions::default())
+            .unwrap()
+            .to_string();
+        assert!(svg.contains("viewBox=\"0 0 700 128\""));
+        assert_eq!(cube.to_facelets(), scrambled);
+    }
+
+    #[test]
+    fn errors() {
+        let cube = Cube::<FourByFour>::new();
+        let solution = Alg::parse("R U // one\nR2 M // two").unwrap();
+
+        let error = cube
+            .draw_solution(&solution, 4, &RenderOptions::default())
+            .unwrap_err();
+        assert_eq!(error.index(), 3);
+        assert!(cube
+            .draw_solution(&Alg::parse("").unwrap(), 0, &RenderOptions::default())
+            .is_ok());
+
+        let options = RenderOptions {
+            accessibility: Some(Accessibility::Glyphs),
+            ..RenderOptions::default()
+        };
+        let svg = cube
+            .draw_solution(&Alg::parse("R // F2L <3 & more").unwrap(), 4, &options)
+            .unwrap()
+            .to_string();
+        assert!(svg.contains("F2L &lt;3 &amp; more\n</text>"));
+        assert!(svg.contains("F2L &lt;3 &amp; more: R.\n</desc>"));
+    }
+}
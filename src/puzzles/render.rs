@@ -0,0 +1,933 @@
+use crate::puzzles::{cube, megaminx};
+use crate::utils::color::Color;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+use svg::node::element::path::{Command, Data, Parameters, Position};
+use svg::node::element::{
+    Circle, Definitions, Description, Group, Path, Pattern, Rectangle, Style, Text, Title, SVG,
+};
+use svg::{Document, Node};
+
+/// How stickers are drawn. Sizes are in SVG user units.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenderOptions {
+    /// Width of a cube sticker or radius of a megaminx face, `None` for the puzzle's own.
+    pub sticker_size: Option<f64>,
+    /// Space between faces, `None` for the puzzle's own.
+    pub gap: Option<f64>,
+    pub stroke: Color,
+    /// Width of the sticker outlines, 0 to draw none.
+    pub stroke_width: f64,
+    /// Radius of the sticker corners, for rectangular stickers.
+    pub corner_radius: f64,
+    /// Space around each sticker, filled with the stroke color like the plastic of a real
+    /// puzzle.
+    pub padding: f64,
+    /// Fill behind the whole image, `None` to leave it transparent.
+    pub background: Option<Color>,
+    /// Colors of the cube and cuboid faces, `None` for the puzzle's own.
+    pub cube_colors: Option<HashMap<cube::Face, Color>>,
+    /// Colors of the megaminx faces, `None` for the puzzle's own.
+    pub megaminx_colors: Option<HashMap<megaminx::Face, Color>>,
+    /// Marks the face each sticker belongs to so that faces can be told apart without their
+    /// colors, and describes the state for screen readers.
+    pub accessibility: Option<Accessibility>,
+    /// Draws the stickers of each face as one path per color, filled through a CSS class,
+    /// instead of an element each.
+    pub compact: bool,
+    /// Decimal places kept in coordinates, `None` to keep them all.
+    pub precision: Option<u8>,
+}
+
+/// Ways of showing which face a sticker belongs to besides its color.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accessibility {
+    /// The name of the face, written on the sticker.
+    Glyphs,
+    /// Hatching drawn over the sticker, with a different angle and style for every face.
+    Patterns,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            sticker_size: None,
+            gap: None,
+            stroke: Color::black(),
+            stroke_width: 1.,
+            corner_radius: 0.,
+            padding: 0.,
+            background: None,
+            cube_colors: None,
+            megaminx_colors: None,
+            accessibility: None,
+            compact: false,
+            precision: None,
+        }
+    }
+}
+
+lazy_static! {
+    static ref THEMES: RwLock<Vec<(String, RenderOptions)>> = RwLock::new(vec![
+        ("light".to_string(), RenderOptions::default()),
+        ("dark".to_string(), RenderOptions::dark()),
+        ("high-contrast".to_string(), RenderOptions::high_contrast()),
+        ("colorblind".to_string(), RenderOptions::colorblind()),
+    ]);
+}
+
+/// Adds a theme that can be picked by name, or replaces the one with the same name.
+pub fn register_theme(name: &str, options: RenderOptions) {
+    let mut themes = THEMES.write().unwrap();
+
+    match themes.iter_mut().find(|(n, _)| n == name) {
+        Some((_, theme)) => *theme = options,
+        None => themes.push((name.to_string(), options)),
+    }
+}
+
+/// Names of every theme, the built-in ones first.
+pub fn themes() -> Vec<String> {
+    THEMES
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// The theme with the given name.
+pub fn theme(name: &str) -> Option<RenderOptions> {
+    THEMES
+        .read()
+        .unwrap()
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, options)| options.clone())
+}
+
+impl RenderOptions {
+    /// Light outlines on a dark background, for pages in dark mode.
+    pub fn dark() -> Self {
+        RenderOptions {
+            stroke: Color::new(0xd0, 0xd0, 0xd0),
+            background: Some(Color::new(0x1e, 0x1e, 0x1e)),
+            ..RenderOptions::default()
+        }
+    }
+
+    /// Stickers sitting on a thick black body, with saturated colors.
+    pub fn high_contrast() -> Self {
+        RenderOptions {
+            stroke_width: 0.,
+            padding: 1.,
+            background: Some(Color::white()),
+            cube_colors: Some(HashMap::from([
+                (cube::Face::B, Color::new(0, 0x40, 0xff)),
+                (cube::Face::D, Color::new(0xff, 0xf0, 0)),
+                (cube::Face::F, Color::new(0, 0xc0, 0x30)),
+                (cube::Face::L, Color::new(0xff, 0x90, 0)),
+                (cube::Face::R, Color::new(0xd0, 0, 0)),
+                (cube::Face::U, Color::white()),
+            ])),
+            ..RenderOptions::default()
+        }
+    }
+
+    /// Colors that stay apart with the common kinds of color blindness, from the Okabe-Ito
+    /// palette, with the name of its face on every sticker.
+    pub fn colorblind() -> Self {
+        RenderOptions {
+            cube_colors: Some(HashMap::from([
+                (cube::Face::B, Color::new(0, 0x72, 0xb2)),
+                (cube::Face::D, Color::new(0xf0, 0xe4, 0x42)),
+                (cube::Face::F, Color::new(0, 0x9e, 0x73)),
+                (cube::Face::L, Color::new(0xcc, 0x79, 0xa7)),
+                (cube::Face::R, Color::new(0xd5, 0x5e, 0)),
+                (cube::Face::U, Color::white()),
+            ])),
+            accessibility: Some(Accessibility::Glyphs),
+            ..RenderOptions::default()
+        }
+    }
+
+    /// Sets the outline of a sticker or polygon.
+    pub(crate) fn stroke<N: Node>(&self, mut node: N) -> N {
+        if self.stroke_width > 0. {
+            node.assign("stroke", self.stroke.to_string());
+            node.assign("stroke-width", self.stroke_width);
+        }
+        node
+    }
+
+    /// Rounds a coordinate to the precision.
+    pub(crate) fn round(&self, x: f64) -> f64 {
+        match self.precision {
+            Some(digits) => {
+                let scale = 10_f64.powi(digits.into());
+                (x * scale).round() / scale
+            }
+            None => x,
+        }
+    }
+}
+
+/// An empty image of the given size, with the background filled in. In accessibility mode it
+/// starts with the title and the description of the state.
+pub(crate) fn document(
+    width: f64,
+    height: f64,
+    options: &RenderOptions,
+    title: &str,
+    description: impl FnOnce() -> String,
+) -> SVG {
+    let mut svg = Document::new()
+        .set("viewBox", (0, 0, width, height))
+        .set("height", "100%")
+        .set("width", "100%");
+
+    if options.accessibility.is_some() {
+        svg = svg
+            .set("role", "img")
+            .add(Title::new().add(svg::node::Text::new(escape(title))))
+            .add(Description::new().add(svg::node::Text::new(escape(&description()))));
+    }
+
+    if let Some(background) = options.background {
+        svg.append(
+            Rectangle::new()
+                .set("width", "100%")
+                .set("height", "100%")
+                .set("fill", background.to_string()),
+        );
+    }
+    svg
+}
+
+/// The text with the characters that have a meaning in XML escaped, as the `svg` crate writes
+/// text as it is given.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Width and height of an image made by [`document`].
+pub(crate) fn size(svg: &SVG) -> (f64, f64) {
+    let view_box = svg.get_attributes().get("viewBox").map(|v| v.to_string());
+    let numbers: Vec<f64> = view_box
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|n| n.parse().ok())
+        .collect();
+
+    match numbers[..] {
+        [_, _, width, height] => (width, height),
+        _ => (0., 0.),
+    }
+}
+
+/// The image nested in another at `(x, y)`, scaled to fit `width` by `height`.
+pub(crate) fn embed(svg: SVG, x: f64, y: f64, width: f64, height: f64) -> SVG {
+    svg.set("x", x)
+        .set("y", y)
+        .set("width", width)
+        .set("height", height)
+}
+
+/// A rectangular sticker taking up the given cell, less the padding.
+fn sticker(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    color: Color,
+    options: &RenderOptions,
+) -> Rectangle {
+    options.stroke(cell(x, y, width, height, options).set("fill", color.to_string()))
+}
+
+/// The outline of a sticker, less the padding.
+fn cell(x: f64, y: f64, width: f64, height: f64, options: &RenderOptions) -> Rectangle {
+    let (x, y, width, height) = inset(x, y, width, height, options);
+
+    let mut rect = Rectangle::new()
+        .set("x", x)
+        .set("y", y)
+        .set("width", width)
+        .set("height", height);
+    if options.corner_radius > 0. {
+        rect = rect.set("rx", options.corner_radius);
+    }
+    rect
+}
+
+/// Position and size of a sticker taking up the given cell, less the padding.
+fn inset(x: f64, y: f64, width: f64, height: f64, options: &RenderOptions) -> (f64, f64, f64, f64) {
+    let padding = options.padding.min(width / 4.).min(height / 4.);
+    (
+        options.round(x + padding),
+        options.round(y + padding),
+        options.round(width - 2. * padding),
+        options.round(height - 2. * padding),
+    )
+}
+
+/// A closed path through the points, rounded to the precision.
+pub(crate) fn outline(points: &[(f64, f64)], options: &RenderOptions) -> Data {
+    let mut data = Data::new();
+    for (i, &(x, y)) in points.iter().enumerate() {
+        let command = if i == 0 { Command::Move } else { Command::Line };
+        data.append(command(
+            Position::Absolute,
+            Parameters::from((options.round(x), options.round(y))),
+        ));
+    }
+    data.append(Command::Close);
+    data
+}
+
+/// The class filling compact stickers with a color.
+fn class(color: Color) -> String {
+    format!("c{}", &color.to_string()[1..])
+}
+
+/// Rules filling the classes of the given colors, `None` unless drawing compact stickers.
+pub(crate) fn palette(
+    colors: impl Iterator<Item = Color>,
+    options: &RenderOptions,
+) -> Option<Style> {
+    if !options.compact {
+        return None;
+    }
+
+    let mut seen = Vec::new();
+    let mut rules = String::new();
+    for color in colors {
+        if !seen.contains(&color) {
+            rules.push_str(&format!(".{}{{fill:{color}}}", class(color)));
+            seen.push(color);
+        }
+    }
+    Some(Style::new(rules))
+}
+
+/// Stickers drawn into a group, each as its own element or, in compact mode, gathered into one
+/// path per color with a subpath per sticker. Stickers keep their own outlines, even next to
+/// one of the same color, unless there are no outlines to keep: square cornered stickers drawn
+/// without any are then merged with those of their color they touch into as few rectangles as
+/// rows and columns allow. The paths go where
+/// the first sticker would have been, so that anything drawn over the stickers in the meantime
+/// stays on top.
+pub(crate) struct Stickers<'a> {
+    options: &'a RenderOptions,
+    start: usize,
+    paths: Vec<(Color, String)>,
+    /// Stickers left to merge by color, as `(x, y, width, height)`.
+    rects: Vec<(Color, Vec<Rect>)>,
+}
+
+type Rect = (f64, f64, f64, f64);
+
+impl<'a> Stickers<'a> {
+    pub(crate) fn new(g: &Group, options: &'a RenderOptions) -> Self {
+        Stickers {
+            options,
+            start: g.get_children().len(),
+            paths: Vec::new(),
+            rects: Vec::new(),
+        }
+    }
+
+    /// Adds a rectangular sticker taking up the given cell, less the padding.
+    pub(crate) fn rect(
+        &mut self,
+        g: &mut Group,
+        (x, y): (f64, f64),
+        (width, height): (f64, f64),
+        color: Color,
+    ) {
+        if !self.options.compact {
+            g.append(sticker(x, y, width, height, color, self.options));
+            return;
+        }
+
+        let (x, y, w, h) = inset(x, y, width, height, self.options);
+        let r = self
+            .options
+            .round(self.options.corner_radius.min(w / 2.).min(h / 2.));
+        if r == 0. && self.options.stroke_width <= 0. {
+            match self.rects.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, rects)) => rects.push((x, y, w, h)),
+                None => self.rects.push((color, vec![(x, y, w, h)])),
+            }
+            self.path(color);
+            return;
+        }
+
+        let path = self.path(color);
+        if r > 0. {
+            let (w, h) = (w - 2. * r, h - 2. * r);
+            path.push_str(&format!(
+                "M{},{y}h{w}a{r},{r} 0 0 1 {r},{r}v{h}a{r},{r} 0 0 1 -{r},{r}h-{w}\
+                 a{r},{r} 0 0 1 -{r},-{r}v-{h}a{r},{r} 0 0 1 {r},-{r}z",
+                x + r
+            ));
+        } else {
+            path.push_str(&format!("M{x},{y}h{w}v{h}h-{w}z"));
+        }
+    }
+
+    /// Adds a sticker with the given corners.
+    pub(crate) fn polygon(&mut self, g: &mut Group, points: &[(f64, f64)], color: Color) {
+        if !self.options.compact {
+            let path = Path::new()
+                .set("fill", color.to_string())
+                .set("d", outline(points, self.options));
+            g.append(self.options.stroke(path));
+            return;
+        }
+
+        let options = self.options;
+        let path = self.path(color);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            path.push_str(&format!(
+                "{command}{},{}",
+                options.round(x),
+                options.round(y)
+            ));
+        }
+        path.push('z');
+    }
+
+    fn path(&mut self, color: Color) -> &mut String {
+        let i = match self.paths.iter().position(|(c, _)| *c == color) {
+            Some(i) => i,
+            None => {
+                self.paths.push((color, String::new()));
+                self.paths.len() - 1
+            }
+        };
+        &mut self.paths[i].1
+    }
+
+    /// Puts the paths of each color in the group, in compact mode.
+    pub(crate) fn finish(mut self, g: &mut Group) {
+        if self.paths.is_empty() {
+            return;
+        }
+
+        for (color, rects) in std::mem::take(&mut self.rects) {
+            let options = self.options;
+            let path = self.path(color);
+            for (x, y, w, h) in merge(rects) {
+                let (w, h) = (options.round(w), options.round(h));
+                path.push_str(&format!("M{x},{y}h{w}v{h}h-{w}z"));
+            }
+        }
+
+        let mut paths = self.options.stroke(Group::new());
+        for (color, d) in self.paths {
+            paths.append(Path::new().set("class", class(color)).set("d", d));
+        }
+        g.get_children_mut().insert(self.start, Box::new(paths));
+    }
+}
+
+/// The rectangles with the runs of them side by side in a row made into one, then the runs of
+/// the same width on top of each other in a column.
+fn merge(mut rects: Vec<Rect>) -> Vec<Rect> {
+    let near = |a: f64, b: f64| (a - b).abs() <= 1e-9 * a.abs().max(b.abs()).max(1.);
+
+    rects.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0)));
+    let mut rows: Vec<Rect> = Vec::new();
+    for (x, y, w, h) in rects {
+        match rows.last_mut() {
+            Some(last) if near(last.1, y) && near(last.3, h) && near(last.0 + last.2, x) => {
+                last.2 = x + w - last.0;
+            }
+            _ => rows.push((x, y, w, h)),
+        }
+    }
+
+    let mut merged: Vec<Rect> = Vec::new();
+    for (x, y, w, h) in rows {
+        let above = merged
+            .iter_mut()
+            .find(|a| near(a.0, x) && near(a.2, w) && near(a.1 + a.3, y));
+        match above {
+            Some(above) => above.3 = y + h - above.1,
+            None => merged.push((x, y, w, h)),
+        }
+    }
+    merged
+}
+
+/// Hatching for the face at `index` in its puzzle, in tiles of `size`: single lines at four
+/// angles, then doubled lines at four angles, then dots, rings, a grid and a diagonal grid, so
+/// that no two of the twelve faces of a megaminx look alike.
+pub(crate) fn pattern(id: &str, index: usize, size: f64, color: Color) -> Pattern {
+    let ink = color.contrasting().to_string();
+    let width = size / 8.;
+    let line = |x: f64| {
+        Path::new()
+            .set("d", format!("M{x},0 V{size}"))
+            .set("stroke", ink.clone())
+            .set("stroke-width", width)
+    };
+    let across = Path::new()
+        .set("d", format!("M0,{} H{size}", size / 2.))
+        .set("stroke", ink.clone())
+        .set("stroke-width", width);
+    let circle = Circle::new()
+        .set("cx", size / 2.)
+        .set("cy", size / 2.)
+        .set("r", size / 6.);
+
+    let angle = match index {
+        0..=7 => 45 * (index % 4),
+        11 => 45,
+        _ => 0,
+    };
+    let pattern = Pattern::new()
+        .set("id", id)
+        .set("patternUnits", "userSpaceOnUse")
+        .set("width", size)
+        .set("height", size)
+        .set("patternTransform", format!("rotate({angle})"))
+        .set("opacity", 0.6);
+
+    match index {
+        0..=3 => pattern.add(line(size / 2.)),
+        4..=7 => pattern.add(line(size / 4.)).add(line(3. * size / 4.)),
+        8 => pattern.add(circle.set("fill", ink)),
+        9 => pattern.add(
+            circle
+                .set("fill", "none")
+                .set("stroke", ink)
+                .set("stroke-width", width),
+        ),
+        _ => pattern.add(line(size / 2.)).add(across),
+    }
+}
+
+/// The id of the pattern for a face, unique to the kind of puzzle.
+pub(crate) fn pattern_id(puzzle: &str, face: impl fmt::Display) -> String {
+    format!("{puzzle}-pattern-{face}")
+}
+
+/// Patterns for every face of a puzzle, given with their index and color, `None` unless
+/// drawing patterns.
+pub(crate) fn patterns<F: fmt::Display>(
+    puzzle: &str,
+    faces: impl Iterator<Item = (usize, F, Color)>,
+    size: f64,
+    options: &RenderOptions,
+) -> Option<Definitions> {
+    if options.accessibility != Some(Accessibility::Patterns) {
+        return None;
+    }
+
+    let mut defs = Definitions::new();
+    for (index, face, color) in faces {
+        defs.append(pattern(&pattern_id(puzzle, face), index, size, color));
+    }
+    Some(defs)
+}
+
+/// Marks a square sticker of the given face and color, with its name in the top left corner
+/// or with the pattern of the face.
+pub(crate) fn mark_face(
+    g: &mut Group,
+    puzzle: &str,
+    face: impl fmt::Display,
+    (x, y): (f64, f64),
+    size: f64,
+    color: Color,
+    options: &RenderOptions,
+) {
+    match options.accessibility {
+        Some(Accessibility::Glyphs) => g.append(
+            Text::new()
+                .set("x", x + 0.1 * size)
+                .set("y", y + 0.1 * size)
+                .set("dominant-baseline", "hanging")
+                .set("font-family", "sans-serif")
+                .set("font-size", 0.35 * size)
+                .set("fill", color.contrasting().to_string())
+                .add(svg::node::Text::new(face.to_string())),
+        ),
+        Some(Accessibility::Patterns) => g.append(
+            cell(x, y, size, size, options)
+                .set("fill", format!("url(#{})", pattern_id(puzzle, face))),
+        ),
+        None => {}
+    }
+}
+
+/// The plastic showing between padded stickers, `None` without padding.
+pub(crate) fn body(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    options: &RenderOptions,
+) -> Option<Rectangle> {
+    (options.padding > 0.).then(|| {
+        Rectangle::new()
+            .set("x", options.round(x))
+            .set("y", options.round(y))
+            .set("width", options.round(width))
+            .set("height", options.round(height))
+            .set("rx", options.corner_radius)
+            .set("fill", options.stroke.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::cube::{Cube, SevenBySeven, ThreeByThree};
+    use crate::puzzles::cuboid::{Cuboid, ThreeByThreeByTwo};
+    use crate::puzzles::megaminx::Megaminx;
+    use crate::puzzles::Puzzle;
+
+    #[test]
+    fn defaults() {
+        let cube = Cube::<ThreeByThree>::new();
+        assert_eq!(
+            cube.draw().to_string(),
+            cube.draw_with(&RenderOptions::default()).to_string()
+        );
+
+        let svg = cube.draw().to_string();
+        assert!(svg.contains("viewBox=\"0 0 130 98\""));
+        assert_eq!(
+            svg.matches("stroke=\"#000000\" stroke-width=\"1\"").count(),
+            54
+        );
+        assert!(!svg.contains("rx="));
+    }
+
+    #[test]
+    fn options() {
+        let options = RenderOptions {
+            sticker_size: Some(20.),
+            gap: Some(5.),
+            stroke: Color::white(),
+            stroke_width: 0.5,
+            corner_radius: 2.,
+            padding: 1.5,
+            background: Some(Color::black()),
+            ..RenderOptions::default()
+        };
+
+        let svg = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert!(svg.contains("viewBox=\"0 0 265 200\""));
+        assert!(svg.contains("<rect fill=\"#000000\" height=\"100%\" width=\"100%\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 1 + 6 + 54);
+        assert_eq!(svg.matches("rx=\"2\"").count(), 6 + 54);
+        assert!(svg.contains("height=\"17\""));
+        assert_eq!(
+            svg.matches("stroke=\"#FFFFFF\" stroke-width=\"0.5\"")
+                .count(),
+            54
+        );
+
+        let svg = Cuboid::<ThreeByThreeByTwo>::new()
+            .draw_with(&options)
+            .to_string();
+        assert_eq!(svg.matches("<rect").count(), 1 + 6 + 42);
+
+        let svg = Megaminx::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<path").count(), 12 + 132);
+        assert_eq!(svg.matches("stroke-width=\"0.5\"").count(), 132);
+    }
+
+    #[test]
+    fn no_stroke() {
+        let options = RenderOptions {
+            stroke_width: 0.,
+            ..RenderOptions::default()
+        };
+
+        let svg = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert!(!svg.contains("stroke"));
+        let svg = Megaminx::new().draw_with(&options).to_string();
+        assert!(!svg.contains("stroke"));
+    }
+
+    #[test]
+    fn themes() {
+        let names = super::themes();
+        assert_eq!(names[..4], ["light", "dark", "high-contrast", "colorblind"]);
+        assert_eq!(theme("light"), Some(RenderOptions::default()));
+        assert_eq!(theme("sepia"), None);
+
+        let dark = theme("dark").unwrap();
+        let svg = Cube::<ThreeByThree>::new().draw_with(&dark).to_string();
+        assert!(svg.contains("fill=\"#1E1E1E\""));
+        assert!(svg.contains("stroke=\"#D0D0D0\""));
+
+        let sepia = RenderOptions {
+            background: Some(Color::new(0xf4, 0xec, 0xd8)),
+            ..RenderOptions::dark()
+        };
+        register_theme("sepia", sepia.clone());
+        assert_eq!(theme("sepia"), Some(sepia));
+        register_theme("sepia", RenderOptions::default());
+        assert_eq!(theme("sepia"), Some(RenderOptions::default()));
+        assert_eq!(super::themes().iter().filter(|n| *n == "sepia").count(), 1);
+    }
+
+    #[test]
+    fn colorblind() {
+        let options = RenderOptions::colorblind();
+
+        let svg = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<text").count(), 54);
+        assert_eq!(svg.matches("\nR\n").count(), 9);
+        assert!(svg.contains("fill=\"#D55E00\""));
+        assert!(!svg.contains(&Color::red().to_string()));
+
+        let svg = Cuboid::<ThreeByThreeByTwo>::new()
+            .draw_with(&options)
+            .to_string();
+        assert_eq!(svg.matches("<text").count(), 42);
+
+        let svg = Megaminx::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<text").count(), 132);
+        assert_eq!(svg.matches("\nDbr\n").count(), 11);
+
+        let svg = Cube::<ThreeByThree>::new()
+            .draw_with(&RenderOptions::high_contrast())
+            .to_string();
+        assert_eq!(svg.matches("<text").count(), 0);
+        assert!(!svg.contains("stroke="));
+    }
+
+    #[test]
+    fn patterns() {
+        let options = RenderOptions {
+            accessibility: Some(Accessibility::Patterns),
+            ..RenderOptions::default()
+        };
+
+        let svg = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<pattern").count(), 6);
+        assert_eq!(svg.matches("url(#cube-pattern-R)").count(), 9);
+        assert_eq!(svg.matches("<text").count(), 0);
+
+        let svg = Cuboid::<ThreeByThreeByTwo>::new()
+            .draw_with(&options)
+            .to_string();
+        assert_eq!(svg.matches("<pattern").count(), 6);
+        assert_eq!(svg.matches("url(#cuboid-pattern-").count(), 42);
+
+        let svg = Megaminx::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<pattern").count(), 12);
+        assert_eq!(svg.matches("url(#megaminx-pattern-Dbr)").count(), 11);
+    }
+
+    /// Which of a grid of points of the plane are inked by a pattern, in tiles of `size`.
+    fn ink(pattern: &str, size: f64) -> Vec<bool> {
+        let attribute = |tag: &str, name: &str| -> Option<f64> {
+            let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+            let value = &tag[start..start + tag[start..].find('"')?];
+            value
+                .trim_start_matches("rotate(")
+                .trim_end_matches(')')
+                .parse()
+                .ok()
+        };
+        let tags: Vec<&str> = pattern.split('<').collect();
+        let angle = attribute(tags[1], "patternTransform").unwrap().to_radians();
+        let half = size / 16.;
+
+        let wrap = |x: f64| x.rem_euclid(size);
+        let near = |a: f64, b: f64| {
+            let d = wrap(a - b);
+            d.min(size - d) < half
+        };
+        let inked = |u: f64, v: f64| {
+            tags.iter().any(|tag| {
+                if tag.starts_with("path") {
+                    let d = &tag[tag.find("d=\"M").unwrap() + 4..];
+                    let (x, rest) = d.split_once(',').unwrap();
+                    let y: String = rest.chars().take_while(|c| *c != ' ').collect();
+                    if d.contains(" V") {
+                        near(u, x.parse().unwrap())
+                    } else {
+                        near(v, y.parse().unwrap())
+                    }
+                } else if tag.starts_with("circle") {
+                    let distance = (u - size / 2.).hypot(v - size / 2.);
+                    let r = attribute(tag, "r").unwrap();
+                    if tag.contains("fill=\"none\"") {
+                        (distance - r).abs() < half
+                    } else {
+                        distance < r
+                    }
+                } else {
+                    false
+                }
+            })
+        };
+
+        let step = size / 16.;
+        let mut points = vec![];
+        for i in 0..64 {
+            for j in 0..64 {
+                let (x, y) = (i as f64 * step + 0.01, j as f64 * step + 0.02);
+                let (u, v) = (
+                    x * angle.cos() + y * angle.sin(),
+                    y * angle.cos() - x * angle.sin(),
+                );
+                points.push(inked(wrap(u), wrap(v)));
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn distinct_patterns() {
+        let size = 8.;
+        let inks: Vec<Vec<bool>> = (0..12)
+            .map(|i| ink(&pattern("p", i, size, Color::white()).to_string(), size))
+            .collect();
+
+        for i in 0..12 {
+            assert!(inks[i].iter().any(|p| *p), "{i}");
+            for j in 0..i {
+                let differing = inks[i].iter().zip(&inks[j]).filter(|(a, b)| a != b);
+                assert!(differing.count() > inks[i].len() / 20, "{i} and {j}");
+            }
+        }
+    }
+
+    #[test]
+    fn compact() {
+        let options = RenderOptions {
+            compact: true,
+            precision: Some(1),
+            ..RenderOptions::default()
+        };
+
+        let mut cube = Cube::<SevenBySeven>::new();
+        cube.apply_scramble("R 3Uw' F2 l D' 2B 3Rw2 U");
+        let full = cube.draw().to_string();
+        let svg = cube.draw_with(&options).to_string();
+        assert!(svg.len() * 3 < full.len());
+        assert!(svg.contains(".cFFFFFF{fill:#FFFFFF}"));
+        assert_eq!(svg.matches("<rect").count(), 0);
+        assert_eq!(svg.matches("stroke=").count(), 6);
+        assert!(svg.matches("<path").count() <= 36);
+
+        let solved = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert_eq!(solved.matches("<path").count(), 6);
+        assert!(solved.contains("<path class=\"cFFFFFF\" d=\"M34,2h10v10h-10zM44,2h10v10h-10z"));
+        // Stickers next to one of the same color keep their own outline.
+        let white = solved.split("class=\"cFFFFFF\" d=\"").nth(1).unwrap();
+        assert_eq!(white[..white.find('"').unwrap()].matches('M').count(), 9);
+
+        let svg = Cuboid::<ThreeByThreeByTwo>::new()
+            .draw_with(&options)
+            .to_string();
+        assert_eq!(svg.matches("<path").count(), 6);
+
+        let full = Megaminx::new().draw().to_string();
+        let svg = Megaminx::new().draw_with(&options).to_string();
+        assert!(svg.len() * 2 < full.len());
+        assert_eq!(svg.matches("<path").count(), 12);
+        for d in svg.split(" d=\"").skip(1) {
+            let d = &d[..d.find('"').unwrap()];
+            for number in d.split(|c: char| !c.is_ascii_digit() && c != '.') {
+                assert!(
+                    number.split('.').nth(1).map_or(0, str::len) <= 1,
+                    "{number}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn merged_compact() {
+        let options = RenderOptions {
+            compact: true,
+            stroke_width: 0.,
+            ..RenderOptions::default()
+        };
+        let subpaths = |svg: &str, color: &str| {
+            let d = svg
+                .split(&format!("class=\"{color}\" d=\""))
+                .nth(1)
+                .unwrap();
+            d[..d.find('"').unwrap()].matches('M').count()
+        };
+
+        let solved = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert_eq!(solved.matches("<path").count(), 6);
+        assert!(solved.contains("<path class=\"cFFFFFF\" d=\"M34,2h30v30h-30z\""));
+        for color in ["cFFFFFF", "cFF0000", "c00FF00"] {
+            assert_eq!(subpaths(&solved, color), 1);
+        }
+
+        let solved = Cube::<SevenBySeven>::new().draw_with(&options).to_string();
+        assert_eq!(subpaths(&solved, "cFFFFFF"), 1);
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.apply_scramble("R2 L2");
+        let svg = cube.draw_with(&options).to_string();
+        assert!(svg.contains("class=\"cFFFFFF\" d=\"M44,2h10v30h-10z\""));
+        assert!(svg.contains("class=\"cFFFF00\" d=\"M34,2h10v30h-10zM54,2h10v30h-10z\""));
+
+        // Padding keeps the stickers apart.
+        let padded = RenderOptions {
+            padding: 1.,
+            ..options
+        };
+        let svg = Cube::<ThreeByThree>::new().draw_with(&padded).to_string();
+        assert_eq!(subpaths(&svg, "cFFFFFF"), 9);
+    }
+
+    #[test]
+    fn rounded_compact() {
+        let options = RenderOptions {
+            compact: true,
+            corner_radius: 2.,
+            padding: 1.,
+            ..RenderOptions::default()
+        };
+
+        let svg = Cube::<ThreeByThree>::new().draw_with(&options).to_string();
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains(
+            "d=\"M37,3h4a2,2 0 0 1 2,2v4a2,2 0 0 1 -2,2h-4a2,2 0 0 1 -2,-2v-4a2,2 0 0 1 2,-2z"
+        ));
+    }
+
+    #[test]
+    fn descriptions() {
+        let svg = Cube::<ThreeByThree>::new().draw().to_string();
+        assert!(!svg.contains("<title>"));
+        assert!(!svg.contains("<desc>"));
+
+        let mut cube = Cube::<ThreeByThree>::new();
+        cube.apply_scramble("R");
+        let svg = cube.draw_with(&RenderOptions::colorblind()).to_string();
+        assert!(svg.contains("role=\"img\""));
+        assert!(svg.contains("<title>\n3x3x3 cube\n</title>"));
+        assert!(svg.contains("U face: U U F, U U F, U U F. R face: R R R"));
+
+        let svg = Megaminx::new()
+            .draw_with(&RenderOptions::colorblind())
+            .to_string();
+        assert!(svg.contains("<title>\nMegaminx\n</title>"));
+        assert!(svg.contains("Dbr face: Dbr Dbr"));
+    }
+}
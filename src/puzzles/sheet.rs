@@ -0,0 +1,430 @@
+use crate::puzzles::render::{document, embed, escape};
+use crate::puzzles::{self, RenderOptions, SheetError};
+use crate::utils::color::Color;
+use svg::node::element::{Rectangle, Text, SVG};
+use svg::Node;
+
+/// Names of the built-in events, as written in headers. Other events go by their id.
+const EVENT_NAMES: [(&str, &str); 10] = [
+    ("222", "2x2x2 Cube"),
+    ("333", "3x3x3 Cube"),
+    ("444", "4x4x4 Cube"),
+    ("555", "5x5x5 Cube"),
+    ("666", "6x6x6 Cube"),
+    ("777", "7x7x7 Cube"),
+    ("MEGA", "Megaminx"),
+    ("223", "2x2x3 Cuboid"),
+    ("332", "3x3x2 Cuboid"),
+    ("334", "3x3x4 Cuboid"),
+];
+
+/// Space around the page, in millimetres like every length of a sheet.
+const MARGIN: f64 = 12.;
+/// Height of the competition and round written at the top of every page.
+const HEADER: f64 = 22.;
+/// Size of the scramble text.
+const FONT_SIZE: f64 = 4.;
+const LINE_HEIGHT: f64 = 5.;
+/// Space between the borders of the cells and what is in them.
+const PADDING: f64 = 2.;
+/// Width of the column numbering the scrambles.
+const NUMBER_WIDTH: f64 = 12.;
+/// Width of the column of images.
+const IMAGE_WIDTH: f64 = 50.;
+/// Least height of a row, for its image to be big enough to check against.
+const ROW_HEIGHT: f64 = 30.;
+/// Height of the heading above the extra scrambles.
+const HEADING_HEIGHT: f64 = 8.;
+
+/// Sizes of paper sheets are printed on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Paper {
+    A4,
+    Letter,
+}
+
+impl Paper {
+    /// Width and height in millimetres.
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            Paper::A4 => (210., 297.),
+            Paper::Letter => (215.9, 279.4),
+        }
+    }
+}
+
+/// The round scrambles are for, written at the top of every page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round {
+    pub competition: String,
+    /// Name of the round, such as `Round 1`.
+    pub name: String,
+    /// Scramble set of the round, such as `A`.
+    pub set: String,
+}
+
+impl Round {
+    /// The event, round and set, such as `3x3x3 Cube Round 1 Scramble Set A`.
+    fn title(&self, event: &str) -> String {
+        let event = EVENT_NAMES
+            .iter()
+            .find(|(id, _)| *id == event)
+            .map_or(event, |(_, name)| name);
+        let set = (!self.set.is_empty()).then(|| format!("Scramble Set {}", self.set));
+
+        let parts = [Some(event.to_string()), Some(self.name.clone()), set];
+        let parts: Vec<String> = parts
+            .into_iter()
+            .flatten()
+            .filter(|p| !p.is_empty())
+            .collect();
+        parts.join(" ")
+    }
+}
+
+enum Row {
+    /// The heading above the extra scrambles.
+    Heading,
+    Scramble {
+        number: String,
+        lines: Vec<String>,
+        image: SVG,
+    },
+}
+
+impl Row {
+    fn height(&self) -> f64 {
+        match self {
+            Row::Heading => HEADING_HEIGHT,
+            Row::Scramble { lines, .. } => {
+                (lines.len() as f64 * LINE_HEIGHT + 2. * PADDING).max(ROW_HEIGHT)
+            }
+        }
+    }
+}
+
+/// Scramble sheets of a round: a table of its scrambles then of its extra scrambles, each with
+/// its number, its moves and the puzzle they leave, over as many pages as it takes. Every page
+/// is headed by the competition and the round, and numbered at the bottom.
+///
+/// Pages are drawn at the size of the paper, with millimetres as user units.
+pub fn draw_sheets(
+    event: &str,
+    round: &Round,
+    scrambles: &[&str],
+    extras: &[&str],
+    paper: Paper,
+    options: &RenderOptions,
+) -> Result<Vec<SVG>, SheetError> {
+    let new = || {
+        puzzles::new(event).ok_or_else(|| SheetError::Event {
+            found: event.to_string(),
+        })
+    };
+    new()?;
+
+    let (width, height) = paper.size();
+    let text_width = width - 2. * MARGIN - NUMBER_WIDTH - IMAGE_WIDTH;
+    // Monospace characters are about 0.6 times as wide as they are high.
+    let columns = ((text_width - 2. * PADDING) / (0.6 * FONT_SIZE)) as usize;
+    let image_options = RenderOptions {
+        background: None,
+        ..options.clone()
+    };
+
+    let numbered = scrambles
+        .iter()
+        .enumerate()
+        .map(|(i, s)| ((i + 1).to_string(), *s));
+    let extra = extras
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (format!("E{}", i + 1), *s));
+
+    let mut rows = vec![];
+    for (number, scramble) in numbered.chain(extra) {
+        if number == "E1" {
+            rows.push(Row::Heading);
+        }
+
+        let mut puzzle = new()?;
+        if let Err(error) = puzzle.try_apply_scramble(scramble) {
+            return Err(SheetError::Scramble { number, error });
+        }
+        rows.push(Row::Scramble {
+            number,
+            lines: wrap(scramble, columns),
+            image: puzzle.draw_with(&image_options),
+        });
+    }
+
+    // Rows go down the page from under the header, the heading staying with the row after it.
+    let top = MARGIN + HEADER;
+    let heights: Vec<f64> = rows.iter().map(Row::height).collect();
+    let mut pages: Vec<Vec<(f64, Row)>> = vec![vec![]];
+    let mut y = top;
+    for (i, row) in rows.into_iter().enumerate() {
+        let needed = match row {
+            Row::Heading => heights[i] + heights.get(i + 1).unwrap_or(&0.),
+            Row::Scramble { .. } => heights[i],
+        };
+        if y + needed > height - MARGIN && pages.last().is_some_and(|p| !p.is_empty()) {
+            pages.push(vec![]);
+            y = top;
+        }
+
+        pages.last_mut().unwrap().push((y, row));
+        y += heights[i];
+    }
+
+    let title = round.title(event);
+    let color = match options.background {
+        Some(background) => background.contrasting(),
+        None => Color::black(),
+    };
+    let count = pages.len();
+
+    let sheets = pages.into_iter().enumerate().map(|(page, rows)| {
+        let footer = format!("Page {} of {count}", page + 1);
+        let mut svg = document(width, height, options, &title, || {
+            format!("{} {title}, {footer}.", round.competition)
+        })
+        .set("width", format!("{width}mm"))
+        .set("height", format!("{height}mm"));
+
+        let center = width / 2.;
+        svg.append(
+            text(center, MARGIN + 7., 7., &round.competition, color).set("font-weight", "bold"),
+        );
+        svg.append(text(center, MARGIN + 15., 5., &title, color));
+        svg.append(text(center, height - MARGIN / 2., 3., &footer, color));
+
+        for (y, row) in rows {
+            let row_height = row.height();
+            let (number, lines, image) = match row {
+                Row::Heading => {
+                    let heading = text(
+                        center,
+                        y + HEADING_HEIGHT - 2.5,
+                        4.5,
+                        "Extra Scrambles",
+                        color,
+                    );
+                    svg.append(heading.set("font-weight", "bold"));
+                    continue;
+                }
+                Row::Scramble {
+                    number,
+                    lines,
+                    image,
+                } => (number, lines, image),
+            };
+
+            let cells = [
+                (MARGIN, NUMBER_WIDTH),
+                (MARGIN + NUMBER_WIDTH, text_width),
+                (width - MARGIN - IMAGE_WIDTH, IMAGE_WIDTH),
+            ];
+            for (x, cell_width) in cells {
+                svg.append(
+                    Rectangle::new()
+                        .set("x", x)
+                        .set("y", y)
+                        .set("width", cell_width)
+                        .set("height", row_height)
+                        .set("fill", "none")
+                        .set("stroke", color.to_string())
+                        .set("stroke-width", 0.3),
+                );
+            }
+
+            let middle = y + row_height / 2.;
+            svg.append(text(
+                MARGIN + NUMBER_WIDTH / 2.,
+                middle + 1.75,
+                5.,
+                &number,
+                color,
+            ));
+
+            let start = middle - lines.len() as f64 * LINE_HEIGHT / 2.;
+            for (i, line) in lines.iter().enumerate() {
+                let baseline = start + i as f64 * LINE_HEIGHT + FONT_SIZE;
+                let line = text(
+                    MARGIN + NUMBER_WIDTH + PADDING,
+                    baseline,
+                    FONT_SIZE,
+                    line,
+                    color,
+                );
+                svg.append(line.set("text-anchor", "start"));
+            }
+
+            svg.append(embed(
+                image,
+                width - MARGIN - IMAGE_WIDTH + PADDING,
+                y + PADDING,
+                IMAGE_WIDTH - 2. * PADDING,
+                row_height - 2. * PADDING,
+            ));
+        }
+        svg
+    });
+
+    Ok(sheets.collect())
+}
+
+/// Monospace text centered on `x`.
+fn text(x: f64, y: f64, size: f64, content: &str, color: Color) -> Text {
+    Text::new()
+        .set("x", x)
+        .set("y", y)
+        .set("text-anchor", "middle")
+        .set("font-family", "monospace")
+        .set("font-size", size)
+        .set("fill", color.to_string())
+        .add(svg::node::Text::new(escape(content)))
+}
+
+/// The lines of the scramble, broken between moves so that they are at most `columns`
+/// characters long where moves allow.
+fn wrap(scramble: &str, columns: usize) -> Vec<String> {
+    let mut lines = vec![];
+
+    for line in scramble.lines() {
+        let mut current = String::new();
+        for mv in line.split_ascii_whitespace() {
+            if !current.is_empty() && current.len() + 1 + mv.len() > columns {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(mv);
+        }
+        if !current.is_empty() {
+            lines.push(current);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzles::ScrambleError;
+
+    fn round() -> Round {
+        Round {
+            competition: "Spring Open & Co".to_string(),
+            name: "Round 1".to_string(),
+            set: "A".to_string(),
+        }
+    }
+
+    #[test]
+    fn one_page() {
+        let scrambles = ["R U R' U'", "F2 D' L", "B U2", "R2", "U' F"];
+        let extras = ["D2 L'", "F R B"];
+        let options = RenderOptions::default();
+
+        let sheets = draw_sheets("333", &round(), &scrambles, &extras, Paper::A4, &options);
+        let sheets = sheets.unwrap();
+        assert_eq!(sheets.len(), 1);
+
+        let sheet = sheets[0].to_string();
+        assert_eq!(sheet.matches("<svg").count(), 8);
+        assert_eq!(sheet.matches("<rect").count(), 7 * (3 + 54));
+        assert!(sheet.contains("width=\"210mm\""));
+        assert!(sheet.contains("viewBox=\"0 0 210 297\""));
+        for text in [
+            "Spring Open &amp; Co",
+            "3x3x3 Cube Round 1 Scramble Set A",
+            "Extra Scrambles",
+            "E2",
+            "F2 D' L",
+            "Page 1 of 1",
+        ] {
+            assert!(sheet.contains(&format!(">\n{text}\n</text>")), "{text}");
+        }
+
+        let sheets = draw_sheets(
+            "OH",
+            &Round::default(),
+            &scrambles,
+            &[],
+            Paper::Letter,
+            &options,
+        );
+        let sheet = sheets.unwrap()[0].to_string();
+        assert!(sheet.contains("width=\"215.9mm\""));
+        assert!(sheet.contains(">\nOH\n</text>"));
+        assert!(!sheet.contains("Extra Scrambles"));
+    }
+
+    #[test]
+    fn pages() {
+        let moves = ["3Rw", "U'", "2F2", "Lw", "3Bw'", "D"];
+        let scramble: Vec<&str> = moves.iter().copied().cycle().take(100).collect();
+        let scramble = scramble.join(" ");
+        let scrambles = [scramble.as_str(); 5];
+
+        let options = RenderOptions::default();
+        let sheets = draw_sheets(
+            "777",
+            &round(),
+            &scrambles,
+            &scrambles[..2],
+            Paper::A4,
+            &options,
+        );
+        let sheets: Vec<String> = sheets.unwrap().iter().map(|s| s.to_string()).collect();
+        assert_eq!(sheets.len(), 2);
+
+        for (i, sheet) in sheets.iter().enumerate() {
+            assert!(sheet.contains("7x7x7 Cube Round 1 Scramble Set A"));
+            assert!(sheet.contains(&format!("Page {} of 2", i + 1)));
+        }
+        let rows: Vec<usize> = sheets
+            .iter()
+            .map(|s| s.matches("<svg").count() - 1)
+            .collect();
+        // The heading goes over to the next page with the first extra scramble.
+        assert_eq!(rows, [5, 2]);
+        assert!(!sheets[0].contains("Extra Scrambles"));
+        assert!(sheets[1].contains("Extra Scrambles"));
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(wrap("R U R' U'", 5), ["R U", "R' U'"]);
+        assert_eq!(wrap("  R2   3Rw'\n\nU  ", 3), ["R2", "3Rw'", "U"]);
+
+        let megaminx = "R++ D-- R++ D++ U\nR-- D-- R-- D++ U'";
+        assert_eq!(wrap(megaminx, 50), megaminx.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn errors() {
+        let options = RenderOptions::default();
+
+        assert_eq!(
+            draw_sheets("333ft", &round(), &[], &[], Paper::A4, &options).unwrap_err(),
+            SheetError::Event {
+                found: "333ft".to_string()
+            }
+        );
+        assert_eq!(
+            draw_sheets("MEGA", &round(), &["U"], &["R++", "R"], Paper::A4, &options).unwrap_err(),
+            SheetError::Scramble {
+                number: "E2".to_string(),
+                error: ScrambleError::Notation {
+                    index: 0,
+                    found: "R".to_string()
+                }
+            }
+        );
+    }
+}
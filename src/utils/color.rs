@@ -1,6 +1,7 @@
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     r: i32,
     g: i32,
@@ -58,4 +59,14 @@ impl Color {
             b: 128,
         }
     }
+
+    /// Black or white, whichever stands out most on this color.
+    pub fn contrasting(&self) -> Self {
+        let luma = 299 * self.r + 587 * self.g + 114 * self.b;
+        if luma > 128_000 {
+            Color::black()
+        } else {
+            Color::white()
+        }
+    }
 }
@@ -17,15 +17,12 @@ where
     matrix.to_owned()
 }
 
+/// The point where the line through `a` and `b` crosses the line through `c` and `d`.
 pub fn get_line_intersection(
-    x1: f64,
-    y1: f64,
-    x2: f64,
-    y2: f64,
-    x3: f64,
-    y3: f64,
-    x4: f64,
-    y4: f64,
+    (x1, y1): (f64, f64),
+    (x2, y2): (f64, f64),
+    (x3, y3): (f64, f64),
+    (x4, y4): (f64, f64),
 ) -> (f64, f64) {
     (
         det(det(x1, y1, x2, y2), x1 - x2, det(x3, y3, x4, y4), x3 - x4)